@@ -0,0 +1,158 @@
+//! Verification of answers against the expected ones stored in `answers.toml`
+//!
+//! The `answers.toml` file lives next to the input files and has a table per input file name:
+//!
+//! ```toml
+//! ["day01.txt"]
+//! part1 = 69836
+//! part2 = "207968"
+//! ```
+//!
+//! Both parts are optional, answers may be written as strings or integers.
+
+use std::collections::HashMap;
+use std::fmt::{self, Display};
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Deserializer};
+
+use crate::solution::Answers;
+
+/// Name of the file with expected answers
+pub const FILE_NAME: &str = "answers.toml";
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Answer {
+    Text(String),
+    Number(i64),
+}
+
+fn answer<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<String>, D::Error> {
+    let answer = Option::<Answer>::deserialize(deserializer)?;
+
+    Ok(answer.map(|answer| match answer {
+        Answer::Text(answer) => answer,
+        Answer::Number(answer) => answer.to_string(),
+    }))
+}
+
+#[derive(Deserialize)]
+struct Expected {
+    #[serde(default, deserialize_with = "answer")]
+    part1: Option<String>,
+    #[serde(default, deserialize_with = "answer")]
+    part2: Option<String>,
+}
+
+/// Parses the content of an answers file, returning expected answers per input file name
+pub fn parse(content: &str) -> Result<HashMap<String, Answers>> {
+    let expected: HashMap<String, Expected> = toml::from_str(content)?;
+
+    let answers = expected
+        .into_iter()
+        .map(|(name, Expected { part1, part2 })| (name, Answers { part1, part2 }))
+        .collect();
+
+    Ok(answers)
+}
+
+/// Loads the expected answers for the input file from `answers.toml` in the same directory
+///
+/// Returns `None` if there is no answers file, or it has no entry for the input. Input read from
+/// stdin has no expected answers.
+pub fn load(input: &Path) -> Result<Option<Answers>> {
+    if input == Path::new(crate::input::STDIN) {
+        return Ok(None);
+    }
+
+    let (Some(dir), Some(name)) = (input.parent(), input.file_name()) else {
+        return Ok(None);
+    };
+
+    let path = dir.join(FILE_NAME);
+    if !path.exists() {
+        return Ok(None);
+    }
+
+    let content = std::fs::read_to_string(&path)
+        .with_context(|| format!("Cannot read answers file {}", path.display()))?;
+    let mut answers =
+        parse(&content).with_context(|| format!("Invalid answers file {}", path.display()))?;
+
+    Ok(name.to_str().and_then(|name| answers.remove(name)))
+}
+
+/// Result of comparing an answer with the expected one
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Pass,
+    Fail { expected: String },
+    Unknown,
+}
+
+impl Verdict {
+    pub fn new(answer: &str, expected: Option<&str>) -> Self {
+        match expected {
+            Some(expected) if expected == answer => Self::Pass,
+            Some(expected) => Self::Fail {
+                expected: expected.to_owned(),
+            },
+            None => Self::Unknown,
+        }
+    }
+
+    pub fn is_fail(&self) -> bool {
+        matches!(self, Self::Fail { .. })
+    }
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Pass => write!(f, "PASS"),
+            Self::Fail { .. } => write!(f, "FAIL"),
+            Self::Unknown => write!(f, "UNKNOWN"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_answers() {
+        let answers = parse(
+            r#"
+            ["my.in"]
+            part1 = 69836
+            part2 = "207968"
+
+            ["test.in"]
+            part2 = """
+            #..
+            .#."""
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            answers["my.in"],
+            Answers {
+                part1: Some("69836".to_owned()),
+                part2: Some("207968".to_owned()),
+            }
+        );
+
+        assert_eq!(answers["test.in"].part1, None);
+    }
+
+    #[test]
+    fn verdicts() {
+        assert_eq!(Verdict::new("42", Some("42")), Verdict::Pass);
+        assert!(Verdict::new("42", Some("41")).is_fail());
+        assert_eq!(Verdict::new("42", None), Verdict::Unknown);
+    }
+}
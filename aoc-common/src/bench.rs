@@ -0,0 +1,119 @@
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use anyhow::{ensure, Result};
+
+use crate::Solution;
+
+/// Timing statistics of a single stage
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+}
+
+impl Stats {
+    /// Calculates statistics of non-empty samples set
+    fn new(mut samples: Vec<Duration>) -> Self {
+        samples.sort_unstable();
+
+        Self {
+            min: samples[0],
+            median: samples[samples.len() / 2],
+            max: samples[samples.len() - 1],
+        }
+    }
+}
+
+/// Timings of all the stages of a single day
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Timings {
+    pub parse: Stats,
+    pub part1: Stats,
+    pub part2: Stats,
+}
+
+impl Timings {
+    /// Stages with their names, in the order they are executed
+    pub fn stages(&self) -> [(&'static str, Stats); 3] {
+        [
+            ("parse", self.parse),
+            ("part1", self.part1),
+            ("part2", self.part2),
+        ]
+    }
+}
+
+fn timed<T>(f: impl FnOnce() -> T) -> (T, Duration) {
+    let start = Instant::now();
+    let result = f();
+    (result, start.elapsed())
+}
+
+/// Runs `f` `iterations` times, measuring every run
+pub fn measure<T>(iterations: usize, mut f: impl FnMut() -> T) -> Result<Stats> {
+    ensure!(iterations > 0, "At least one iteration is required");
+
+    let samples = (0..iterations)
+        .map(|_| timed(|| black_box(f())).1)
+        .collect();
+    Ok(Stats::new(samples))
+}
+
+/// Parses the input and solves both parts `iterations` times, measuring every stage separately
+pub fn bench<S: Solution>(input: &str, iterations: usize) -> Result<Timings> {
+    ensure!(iterations > 0, "At least one iteration is required");
+
+    let mut parse = Vec::with_capacity(iterations);
+    let mut part1 = Vec::with_capacity(iterations);
+    let mut part2 = Vec::with_capacity(iterations);
+
+    for _ in 0..iterations {
+        let (parsed, t) = timed(|| S::parse(black_box(input)));
+        let parsed = parsed?;
+        parse.push(t);
+
+        let (answer, t) = timed(|| S::part1(&parsed).map(black_box));
+        answer?;
+        part1.push(t);
+
+        let (answer, t) = timed(|| S::part2(&parsed).map(black_box));
+        answer?;
+        part2.push(t);
+    }
+
+    Ok(Timings {
+        parse: Stats::new(parse),
+        part1: Stats::new(part1),
+        part2: Stats::new(part2),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stats() {
+        let samples = [5, 1, 4, 2, 3].map(Duration::from_millis).to_vec();
+
+        assert_eq!(
+            Stats::new(samples),
+            Stats {
+                min: Duration::from_millis(1),
+                median: Duration::from_millis(3),
+                max: Duration::from_millis(5),
+            }
+        );
+    }
+
+    #[test]
+    fn measuring() {
+        let stats = measure(3, || std::thread::sleep(Duration::from_millis(1))).unwrap();
+
+        assert!(stats.min >= Duration::from_millis(1));
+        assert!(stats.min <= stats.median && stats.median <= stats.max);
+        assert!(measure(0, || ()).is_err());
+    }
+}
@@ -0,0 +1,342 @@
+use std::fmt::{self, Display};
+use std::ops::{Index, IndexMut};
+
+use crate::{Dir, ParseError, Source};
+
+/// Offsets of all eight surrounding cells, orthogonal ones first
+const OFFSETS8: [(i8, i8); 8] = [
+    (0, -1),
+    (0, 1),
+    (-1, 0),
+    (1, 0),
+    (-1, -1),
+    (1, -1),
+    (-1, 1),
+    (1, 1),
+];
+
+/// Dense 2D grid stored row by row
+///
+/// Cells are addressed with `(x, y)` pairs, where `x` is a column and `y` is a row.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    data: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+impl<T> Grid<T> {
+    /// Creates `width` x `height` grid with every cell set to `fill`
+    pub fn new(width: usize, height: usize, fill: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            data: vec![fill; width * height],
+            width,
+            height,
+        }
+    }
+
+    /// Builds a grid from row-major data
+    ///
+    /// Returns `None` if the data cannot be split into rows of `width` cells.
+    pub fn from_vec(data: Vec<T>, width: usize) -> Option<Self> {
+        if width == 0 {
+            return data.is_empty().then_some(Self {
+                data,
+                width: 0,
+                height: 0,
+            });
+        }
+
+        if !data.len().is_multiple_of(width) {
+            return None;
+        }
+
+        let height = data.len() / width;
+        Some(Self {
+            data,
+            width,
+            height,
+        })
+    }
+
+    /// Parses a map with a character per cell, `cell` returns `None` for invalid characters
+    ///
+    /// All the lines have to be of the same width, `expected` describes a valid cell.
+    pub fn parse(
+        input: &str,
+        expected: &str,
+        mut cell: impl FnMut(char) -> Option<T>,
+    ) -> Result<Self, ParseError> {
+        let source = Source::new(input);
+        let width = input.lines().next().map_or(0, |line| line.chars().count());
+        let mut data = Vec::with_capacity(input.len());
+
+        for line in input.lines() {
+            let mut row = 0;
+            for (idx, c) in line.char_indices() {
+                if row == width {
+                    return Err(source.error(&line[idx..], format!("row of width {width}")));
+                }
+
+                data.push(cell(c).ok_or_else(|| source.error(&line[idx..], expected))?);
+                row += 1;
+            }
+
+            if row < width {
+                return Err(source.error_after(line, format!("row of width {width}")));
+            }
+        }
+
+        Ok(Self::from_vec(data, width).expect("all the rows have the same width"))
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Checks if `(x, y)` lies inside the grid
+    pub fn contains(&self, (x, y): (usize, usize)) -> bool {
+        x < self.width && y < self.height
+    }
+
+    pub fn get(&self, pos: (usize, usize)) -> Option<&T> {
+        self.index_of(pos).map(|idx| &self.data[idx])
+    }
+
+    pub fn get_mut(&mut self, pos: (usize, usize)) -> Option<&mut T> {
+        self.index_of(pos).map(|idx| &mut self.data[idx])
+    }
+
+    /// Iterates over all cells in row-major order together with their coordinates
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), &T)> + '_ {
+        self.data
+            .iter()
+            .enumerate()
+            .map(|(idx, cell)| (self.pos_of(idx), cell))
+    }
+
+    /// Iterates over all the coordinates in row-major order
+    pub fn positions(&self) -> impl Iterator<Item = (usize, usize)> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+
+    /// Row `y` as a slice
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.data[y * self.width..(y + 1) * self.width]
+    }
+
+    /// Iterates over rows, top to bottom
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> + '_ {
+        self.data.chunks(self.width.max(1))
+    }
+
+    /// Iterates over the cells of column `x`, top to bottom
+    pub fn column(&self, x: usize) -> impl DoubleEndedIterator<Item = &T> + '_ {
+        assert!(x < self.width, "column {x} out of {} columns", self.width);
+        self.data[x..].iter().step_by(self.width)
+    }
+
+    /// Position one step away from `pos` by `(dx, dy)`, if it is inside the grid
+    pub fn offset(&self, (x, y): (usize, usize), (dx, dy): (i8, i8)) -> Option<(usize, usize)> {
+        let pos = (
+            x.checked_add_signed(dx.into())?,
+            y.checked_add_signed(dy.into())?,
+        );
+        self.contains(pos).then_some(pos)
+    }
+
+    /// Orthogonal neighbors of `pos` inside the grid
+    pub fn neighbors4(&self, pos: (usize, usize)) -> impl Iterator<Item = (usize, usize)> + '_ {
+        Dir::ALL
+            .into_iter()
+            .filter_map(move |dir| self.offset(pos, dir.offset()))
+    }
+
+    /// Orthogonal and diagonal neighbors of `pos` inside the grid
+    pub fn neighbors8(&self, pos: (usize, usize)) -> impl Iterator<Item = (usize, usize)> + '_ {
+        OFFSETS8
+            .into_iter()
+            .filter_map(move |offset| self.offset(pos, offset))
+    }
+
+    /// Positions from `pos` (excluded) in the direction `dir` up to the grid edge
+    pub fn ray(&self, pos: (usize, usize), dir: Dir) -> impl Iterator<Item = (usize, usize)> + '_ {
+        std::iter::successors(Some(pos), move |pos| self.offset(*pos, dir.offset())).skip(1)
+    }
+
+    /// Resizes the grid to `height` rows, new rows are filled with `fill`
+    pub fn resize_rows(&mut self, height: usize, fill: T)
+    where
+        T: Clone,
+    {
+        self.data.resize(height * self.width, fill);
+        self.height = height;
+    }
+
+    /// Renders the grid with a character per cell, a line per row
+    pub fn render(&self, mut cell: impl FnMut(&T) -> char) -> String {
+        let mut out = String::with_capacity(self.data.len() + self.height);
+
+        for row in self.rows() {
+            out.extend(row.iter().map(&mut cell));
+            out.push('\n');
+        }
+
+        out
+    }
+
+    /// Builds a new grid of the same shape with `f` applied to every cell
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            data: self.data.iter().map(f).collect(),
+            width: self.width,
+            height: self.height,
+        }
+    }
+
+    /// Position of the first cell matching the predicate, in row-major order
+    pub fn position(&self, pred: impl FnMut(&T) -> bool) -> Option<(usize, usize)> {
+        self.data.iter().position(pred).map(|idx| self.pos_of(idx))
+    }
+
+    fn index_of(&self, pos: (usize, usize)) -> Option<usize> {
+        self.contains(pos).then(|| pos.1 * self.width + pos.0)
+    }
+
+    fn pos_of(&self, idx: usize) -> (usize, usize) {
+        (idx % self.width, idx / self.width)
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: (usize, usize)) -> &T {
+        match self.get(pos) {
+            Some(cell) => cell,
+            None => panic!(
+                "position {pos:?} out of {}x{} grid",
+                self.width, self.height
+            ),
+        }
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, pos: (usize, usize)) -> &mut T {
+        let (width, height) = (self.width, self.height);
+        match self.get_mut(pos) {
+            Some(cell) => cell,
+            None => panic!("position {pos:?} out of {width}x{height} grid"),
+        }
+    }
+}
+
+/// Prints the cells row by row, a line per row
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn non_square_indexing() {
+        let grid = Grid::from_vec(vec![0, 1, 2, 3, 4, 5], 3).unwrap();
+
+        assert_eq!(grid.width(), 3);
+        assert_eq!(grid.height(), 2);
+        assert_eq!(grid[(2, 0)], 2);
+        assert_eq!(grid[(0, 1)], 3);
+        assert_eq!(grid.get((3, 0)), None);
+        assert_eq!(grid.position(|c| *c == 5), Some((2, 1)));
+    }
+
+    #[test]
+    fn ragged_data() {
+        assert_eq!(Grid::from_vec(vec![0; 5], 2), None);
+    }
+
+    #[test]
+    fn parsing() {
+        let digit = |c: char| c.to_digit(10);
+        let grid = Grid::parse("123\n456\n", "digit", digit).unwrap();
+
+        assert_eq!(grid, Grid::from_vec(vec![1, 2, 3, 4, 5, 6], 3).unwrap());
+        assert_eq!(grid.to_string(), "123\n456\n");
+        assert_eq!(
+            grid.render(|d| if d % 2 == 0 { '#' } else { '.' }),
+            ".#.\n#.#\n"
+        );
+
+        assert_eq!(
+            Grid::parse("123\n4x6", "digit", digit),
+            Err(ParseError::new(2, 2, "digit"))
+        );
+        assert_eq!(
+            Grid::parse("123\n45", "digit", digit),
+            Err(ParseError::new(2, 3, "row of width 3"))
+        );
+        assert_eq!(
+            Grid::parse("123\n4567", "digit", digit),
+            Err(ParseError::new(2, 4, "row of width 3"))
+        );
+    }
+
+    #[test]
+    fn lines() {
+        let grid = Grid::from_vec((0..12).collect(), 4).unwrap();
+
+        assert_eq!(grid.row(1), &[4, 5, 6, 7]);
+        assert_eq!(grid.rows().count(), 3);
+        assert_eq!(grid.column(2).copied().collect::<Vec<_>>(), vec![2, 6, 10]);
+        assert_eq!(grid.column(3).next_back(), Some(&11));
+        assert_eq!(
+            grid.ray((1, 1), Dir::Right).collect::<Vec<_>>(),
+            vec![(2, 1), (3, 1)]
+        );
+        assert_eq!(grid.ray((1, 1), Dir::Up).collect::<Vec<_>>(), vec![(1, 0)]);
+        assert_eq!(grid.ray((0, 2), Dir::Down).count(), 0);
+    }
+
+    #[test]
+    fn neighborhoods() {
+        let grid = Grid::new(3, 2, ());
+
+        let mut n4: Vec<_> = grid.neighbors4((0, 0)).collect();
+        n4.sort();
+        assert_eq!(n4, vec![(0, 1), (1, 0)]);
+
+        let mut n8: Vec<_> = grid.neighbors8((1, 1)).collect();
+        n8.sort();
+        assert_eq!(n8, vec![(0, 0), (0, 1), (1, 0), (2, 0), (2, 1)]);
+    }
+
+    #[test]
+    fn growing() {
+        let mut grid = Grid::new(2, 1, 0);
+        grid.resize_rows(3, 1);
+
+        assert_eq!(grid.height(), 3);
+        assert_eq!(grid[(1, 2)], 1);
+        assert_eq!(grid[(1, 0)], 0);
+    }
+}
@@ -0,0 +1,59 @@
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+
+/// Directory with the puzzle inputs, relative to the repository root
+pub const DIR: &str = "inputs";
+
+/// Input path standing for stdin
+pub const STDIN: &str = "-";
+
+/// Conventional location of the day puzzle input: `inputs/dayNN.txt`
+pub fn default_path(day: u8) -> PathBuf {
+    Path::new(DIR).join(format!("day{day:02}.txt"))
+}
+
+/// Reads the whole puzzle input from stdin
+pub fn stdin() -> Result<String> {
+    let mut input = String::new();
+    std::io::stdin().lock().read_to_string(&mut input)?;
+    Ok(input)
+}
+
+/// Reads the whole puzzle input from the file, or from stdin if the path is `-`
+pub fn read(path: &Path) -> Result<String> {
+    if path == Path::new(STDIN) {
+        return stdin();
+    }
+
+    std::fs::read_to_string(path)
+        .with_context(|| format!("Cannot read input file {}", path.display()))
+}
+
+/// Opens the puzzle input for reading in pieces, stdin if the path is `-`
+pub fn open(path: &Path) -> Result<Box<dyn Read>> {
+    if path == Path::new(STDIN) {
+        return Ok(Box::new(std::io::stdin().lock()));
+    }
+
+    let file = std::fs::File::open(path)
+        .with_context(|| format!("Cannot open input file {}", path.display()))?;
+    Ok(Box::new(file))
+}
+
+/// Iterates over input lines, skipping the empty ones
+pub fn non_empty_lines(input: &str) -> impl Iterator<Item = &str> {
+    input.lines().filter(|line| !line.trim().is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn paths() {
+        assert_eq!(default_path(7), Path::new("inputs/day07.txt"));
+        assert_eq!(default_path(21), Path::new("inputs/day21.txt"));
+    }
+}
@@ -0,0 +1,248 @@
+//! Closed integer intervals and sets of them
+//!
+//! An [`IntervalSet`] keeps its intervals sorted and merges the overlapping and adjacent ones, so
+//! coverage of many ranges is computed without visiting their values one by one.
+
+use std::fmt::Display;
+use std::ops::{Add, Sub};
+
+/// Integer type intervals can be made of
+pub trait Integer: Copy + Ord + Add<Output = Self> + Sub<Output = Self> + From<u8> {}
+
+impl<T> Integer for T where T: Copy + Ord + Add<Output = T> + Sub<Output = T> + From<u8> {}
+
+/// Closed interval `start..=end`, never empty
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Interval<T> {
+    pub start: T,
+    pub end: T,
+}
+
+impl<T: Integer> Interval<T> {
+    /// # Panics
+    ///
+    /// Panics if `start` is greater than `end`.
+    pub fn new(start: T, end: T) -> Self {
+        assert!(start <= end, "interval start greater than its end");
+        Interval { start, end }
+    }
+
+    /// Number of values in the interval
+    pub fn len(&self) -> T {
+        self.end - self.start + T::from(1)
+    }
+
+    pub fn contains_value(&self, value: T) -> bool {
+        self.start <= value && value <= self.end
+    }
+
+    /// Whether the other interval lies completely within this one
+    pub fn contains(&self, other: &Self) -> bool {
+        self.start <= other.start && other.end <= self.end
+    }
+
+    /// Whether the intervals share at least one value
+    pub fn overlaps(&self, other: &Self) -> bool {
+        self.start <= other.end && other.start <= self.end
+    }
+
+    /// Whether the intervals overlap, or one starts right after the other ends
+    pub fn touches(&self, other: &Self) -> bool {
+        let one = T::from(1);
+
+        self.overlaps(other)
+            || (self.end < other.start && other.start - self.end == one)
+            || (other.end < self.start && self.start - other.end == one)
+    }
+
+    /// Values in both intervals, `None` if they don't overlap
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        self.overlaps(other)
+            .then(|| Interval::new(self.start.max(other.start), self.end.min(other.end)))
+    }
+
+    /// Values in any of the intervals, `None` if they don't form a single interval
+    pub fn union(&self, other: &Self) -> Option<Self> {
+        self.touches(other)
+            .then(|| Interval::new(self.start.min(other.start), self.end.max(other.end)))
+    }
+
+    /// Values between the intervals, `None` if there are none
+    pub fn gap(&self, other: &Self) -> Option<Self> {
+        let (fst, snd) = if self.end < other.start {
+            (self, other)
+        } else {
+            (other, self)
+        };
+
+        (!self.touches(other)).then(|| Interval::new(fst.end + T::from(1), snd.start - T::from(1)))
+    }
+}
+
+impl<T: Display> Display for Interval<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}-{}", self.start, self.end)
+    }
+}
+
+/// Set of values given by sorted, disjoint and non-adjacent intervals
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IntervalSet<T> {
+    intervals: Vec<Interval<T>>,
+}
+
+impl<T> Default for IntervalSet<T> {
+    fn default() -> Self {
+        IntervalSet { intervals: vec![] }
+    }
+}
+
+impl<T: Integer> IntervalSet<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds all the values of the interval, merging it with the touching intervals in the set
+    pub fn insert(&mut self, interval: Interval<T>) {
+        let from = self
+            .intervals
+            .partition_point(|i| i.end < interval.start && !i.touches(&interval));
+        let to = self
+            .intervals
+            .partition_point(|i| i.start <= interval.end || i.touches(&interval));
+
+        let merged = self.intervals[from..to]
+            .iter()
+            .fold(interval, |merged, i| merged.union(i).unwrap());
+
+        self.intervals.splice(from..to, [merged]);
+    }
+
+    /// Number of values in the set
+    pub fn len(&self) -> T {
+        self.intervals
+            .iter()
+            .fold(T::from(0), |len, interval| len + interval.len())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    pub fn contains_value(&self, value: T) -> bool {
+        let idx = self.intervals.partition_point(|i| i.end < value);
+        self.intervals
+            .get(idx)
+            .is_some_and(|i| i.contains_value(value))
+    }
+
+    /// Intervals of the set, sorted
+    pub fn intervals(&self) -> &[Interval<T>] {
+        &self.intervals
+    }
+
+    /// Intervals between the first and the last value of the set which are not in the set
+    pub fn gaps(&self) -> impl Iterator<Item = Interval<T>> + '_ {
+        self.intervals.windows(2).filter_map(|w| w[0].gap(&w[1]))
+    }
+}
+
+impl<T: Integer> FromIterator<Interval<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = Interval<T>>>(iter: I) -> Self {
+        let mut set = IntervalSet::new();
+        for interval in iter {
+            set.insert(interval);
+        }
+
+        set
+    }
+}
+
+/// Maximal number of the intervals sharing a single value
+pub fn max_depth<T: Integer>(intervals: &[Interval<T>]) -> usize {
+    let mut bounds: Vec<_> = intervals
+        .iter()
+        .flat_map(|i| [(i.start, false), (i.end, true)])
+        .collect();
+    // Intervals are closed, so the ones starting at a value are counted before the ones ending there
+    bounds.sort();
+
+    let mut depth = 0;
+    bounds
+        .into_iter()
+        .map(|(_, end)| {
+            if end {
+                depth -= 1;
+            } else {
+                depth += 1;
+            }
+
+            depth
+        })
+        .max()
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn iv(start: i32, end: i32) -> Interval<i32> {
+        Interval::new(start, end)
+    }
+
+    #[test]
+    fn interval_algebra() {
+        assert_eq!(iv(2, 8).len(), 7);
+        assert!(iv(2, 8).contains(&iv(3, 7)));
+        assert!(!iv(3, 7).contains(&iv(2, 8)));
+        assert!(iv(5, 7).overlaps(&iv(7, 9)));
+        assert!(!iv(2, 3).overlaps(&iv(4, 5)));
+        assert!(iv(2, 3).touches(&iv(4, 5)));
+
+        assert_eq!(iv(5, 7).intersection(&iv(7, 9)), Some(iv(7, 7)));
+        assert_eq!(iv(2, 4).intersection(&iv(6, 8)), None);
+        assert_eq!(iv(2, 3).union(&iv(4, 5)), Some(iv(2, 5)));
+        assert_eq!(iv(2, 4).union(&iv(6, 8)), None);
+        assert_eq!(iv(6, 8).gap(&iv(2, 4)), Some(iv(5, 5)));
+        assert_eq!(iv(2, 3).gap(&iv(4, 5)), None);
+        assert_eq!(iv(-3, -1).to_string(), "-3--1");
+
+        let unsigned = Interval::new(0u128, 0);
+        assert_eq!(
+            unsigned
+                .gap(&Interval::new(u128::MAX, u128::MAX))
+                .unwrap()
+                .len(),
+            u128::MAX - 1
+        );
+    }
+
+    #[test]
+    fn merging() {
+        let mut set: IntervalSet<_> = [iv(10, 12), iv(1, 2), iv(5, 6)].into_iter().collect();
+        assert_eq!(set.intervals(), [iv(1, 2), iv(5, 6), iv(10, 12)]);
+        assert_eq!(set.gaps().collect::<Vec<_>>(), [iv(3, 4), iv(7, 9)]);
+
+        set.insert(iv(3, 3));
+        set.insert(iv(7, 11));
+        assert_eq!(set.intervals(), [iv(1, 3), iv(5, 12)]);
+        assert_eq!(set.len(), 11);
+
+        assert!(set.contains_value(5));
+        assert!(!set.contains_value(4));
+        assert!(!set.contains_value(13));
+
+        set.insert(iv(0, 20));
+        assert_eq!(set.intervals(), [iv(0, 20)]);
+        assert!(IntervalSet::<i32>::new().is_empty());
+    }
+
+    #[test]
+    fn depth() {
+        assert_eq!(max_depth::<i32>(&[]), 0);
+        assert_eq!(max_depth(&[iv(1, 2), iv(3, 4)]), 1);
+        assert_eq!(max_depth(&[iv(1, 3), iv(3, 4), iv(2, 3)]), 3);
+        assert_eq!(max_depth(&[iv(1, 9), iv(2, 3), iv(4, 5), iv(5, 6)]), 3);
+    }
+}
@@ -0,0 +1,19 @@
+//! Utilities shared by all the days: input loading and parse errors, 2D grid, coordinates, graph
+//! searches, integer intervals, the common `Solution` interface, benchmarking and answers
+//! verification.
+
+pub mod answers;
+pub mod bench;
+pub mod grid;
+pub mod input;
+pub mod interval;
+pub mod parse;
+pub mod pos;
+pub mod search;
+pub mod solution;
+
+pub use grid::Grid;
+pub use interval::{Interval, IntervalSet};
+pub use parse::{ParseError, Source};
+pub use pos::{Dir, Pos};
+pub use solution::Solution;
@@ -0,0 +1,169 @@
+//! Structured errors of puzzle input parsing
+//!
+//! Parsers work on subslices of the whole input (lines, tokens, nom leftovers), so the error
+//! position is recovered from where the offending subslice lies in the input. [`Source`] keeps the
+//! whole input around for that and offers the small helpers most of the parsers need. With the
+//! `nom` feature it also converts nom errors.
+
+use std::fmt::{self, Display};
+use std::str::FromStr;
+
+/// Error pointing to the place in the input where parsing failed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// Line number, starting from 1
+    pub line: usize,
+    /// Column number in characters, starting from 1
+    pub column: usize,
+    /// Description of what was expected at this position
+    pub expected: String,
+}
+
+impl ParseError {
+    pub fn new(line: usize, column: usize, expected: impl Into<String>) -> Self {
+        Self {
+            line,
+            column,
+            expected: expected.into(),
+        }
+    }
+
+    /// Error at the beginning of `at`, which is a subslice of `input`
+    ///
+    /// If `at` does not point into `input`, it is assumed to be its suffix.
+    pub fn at(input: &str, at: &str, expected: impl Into<String>) -> Self {
+        let start = input.as_ptr() as usize;
+        let pos = at.as_ptr() as usize;
+
+        let offset = if (start..=start + input.len()).contains(&pos) {
+            pos - start
+        } else {
+            input.len().saturating_sub(at.len())
+        };
+
+        let before = &input[..offset];
+        let line_start = before.rfind('\n').map_or(0, |idx| idx + 1);
+
+        Self::new(
+            before.matches('\n').count() + 1,
+            before[line_start..].chars().count() + 1,
+            expected,
+        )
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: expected {}",
+            self.line, self.column, self.expected
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// The whole parsed input, locating errors found in its subslices
+#[derive(Debug, Clone, Copy)]
+pub struct Source<'a> {
+    input: &'a str,
+}
+
+impl<'a> Source<'a> {
+    pub fn new(input: &'a str) -> Self {
+        Self { input }
+    }
+
+    /// Error at the beginning of `at`
+    pub fn error(&self, at: &str, expected: impl Into<String>) -> ParseError {
+        ParseError::at(self.input, at, expected)
+    }
+
+    /// Error right after the end of `at`
+    pub fn error_after(&self, at: &str, expected: impl Into<String>) -> ParseError {
+        self.error(&at[at.len()..], expected)
+    }
+
+    /// Parses the whole token as a number
+    pub fn number<T: FromStr>(&self, token: &str) -> Result<T, ParseError> {
+        token.parse().map_err(|_| self.error(token, "number"))
+    }
+
+    /// Strips the expected prefix from `s`
+    pub fn strip_prefix(&self, s: &'a str, prefix: &str) -> Result<&'a str, ParseError> {
+        s.strip_prefix(prefix)
+            .ok_or_else(|| self.error(s, format!("`{prefix}`")))
+    }
+
+    /// Splits `s` on the first occurrence of `delim`, which is expected to be there
+    pub fn split_once(&self, s: &'a str, delim: &str) -> Result<(&'a str, &'a str), ParseError> {
+        s.split_once(delim)
+            .ok_or_else(|| self.error_after(s, format!("`{delim}`")))
+    }
+}
+
+#[cfg(feature = "nom")]
+impl Source<'_> {
+    /// Error at the place where the nom parser failed
+    pub fn nom_error(
+        &self,
+        err: nom::Err<nom::error::Error<&str>>,
+        expected: impl Into<String>,
+    ) -> ParseError {
+        match err {
+            nom::Err::Error(err) | nom::Err::Failure(err) => self.error(err.input, expected),
+            nom::Err::Incomplete(_) => self.error_after(self.input, expected),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn error_position() {
+        let input = "abc\ndéf\nghi";
+        let line = input.lines().nth(1).unwrap();
+
+        assert_eq!(
+            ParseError::at(input, input, "x"),
+            ParseError::new(1, 1, "x")
+        );
+        assert_eq!(
+            ParseError::at(input, &line[3..], "x"),
+            ParseError::new(2, 3, "x")
+        );
+        assert_eq!(
+            ParseError::at(input, &input[input.len()..], "x"),
+            ParseError::new(3, 4, "x")
+        );
+        assert_eq!(ParseError::at(input, "hi", "x"), ParseError::new(3, 2, "x"));
+    }
+
+    #[test]
+    fn source_helpers() {
+        let input = "a: 12\nb; x";
+        let source = Source::new(input);
+        let mut lines = input.lines();
+
+        let (name, value) = source.split_once(lines.next().unwrap(), ": ").unwrap();
+        assert_eq!(name, "a");
+        assert_eq!(source.number::<u32>(value), Ok(12));
+
+        let line = lines.next().unwrap();
+        assert_eq!(
+            source.split_once(line, ": "),
+            Err(ParseError::new(2, 5, "`: `"))
+        );
+        assert_eq!(
+            source.number::<u32>(&line[3..]),
+            Err(ParseError::new(2, 4, "number"))
+        );
+        assert_eq!(
+            source.strip_prefix(line, "a"),
+            Err(ParseError::new(2, 1, "`a`"))
+        );
+    }
+}
@@ -0,0 +1,85 @@
+use std::ops::{Add, Sub};
+
+/// 2D coordinates
+///
+/// Coordinates follow the screen convention used by all the puzzle maps: `x` grows to the right,
+/// `y` grows downwards.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Pos<T> {
+    pub x: T,
+    pub y: T,
+}
+
+impl<T> Pos<T> {
+    pub const fn new(x: T, y: T) -> Self {
+        Self { x, y }
+    }
+}
+
+impl<T> Pos<T>
+where
+    T: Copy + Ord + Add<Output = T> + Sub<Output = T>,
+{
+    /// Manhattan (taxicab) distance between two points
+    pub fn manhattan(self, other: Self) -> T {
+        let dx = self.x.max(other.x) - self.x.min(other.x);
+        let dy = self.y.max(other.y) - self.y.min(other.y);
+        dx + dy
+    }
+}
+
+impl<T> Pos<T>
+where
+    T: Copy + Add<Output = T> + From<i8>,
+{
+    /// Position one step away in the given direction
+    pub fn step(self, dir: Dir) -> Self {
+        let (dx, dy) = dir.offset();
+        Self::new(self.x + dx.into(), self.y + dy.into())
+    }
+}
+
+impl<T: Add<Output = T>> Add for Pos<T> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Pos<T> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Self::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl<T> From<(T, T)> for Pos<T> {
+    fn from((x, y): (T, T)) -> Self {
+        Self::new(x, y)
+    }
+}
+
+/// One of four orthogonal directions
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Dir {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl Dir {
+    pub const ALL: [Dir; 4] = [Dir::Up, Dir::Down, Dir::Left, Dir::Right];
+
+    /// `(dx, dy)` of a single step in this direction
+    pub fn offset(self) -> (i8, i8) {
+        match self {
+            Dir::Up => (0, -1),
+            Dir::Down => (0, 1),
+            Dir::Left => (-1, 0),
+            Dir::Right => (1, 0),
+        }
+    }
+}
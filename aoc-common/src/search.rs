@@ -0,0 +1,324 @@
+//! Graph searches over nodes given by their neighbors
+//!
+//! Graphs are never stored explicitly, searches query the [`Neighbors`] of every visited node
+//! instead. It is implemented for closures returning neighbors of a node, so most of the time the
+//! graph is just a closure looking into the puzzle map. Such a closure needs its argument type
+//! annotated, as in `|p: &Pos| ...`, the searches cannot infer it.
+
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::hash::Hash;
+
+use crate::Grid;
+
+/// Graph given by neighbors of its nodes
+///
+/// `E` is an edge - for unweighted graphs it is just the neighbor node, weighted graphs yield
+/// `(node, cost)` pairs.
+pub trait Neighbors<N, E = N> {
+    type Edges: IntoIterator<Item = E>;
+
+    fn neighbors(&mut self, node: &N) -> Self::Edges;
+}
+
+impl<N, E, I, F> Neighbors<N, E> for F
+where
+    F: FnMut(&N) -> I,
+    I: IntoIterator<Item = E>,
+{
+    type Edges = I;
+
+    fn neighbors(&mut self, node: &N) -> I {
+        self(node)
+    }
+}
+
+/// Search result: cost of reaching every visited node, and the node it was reached from
+#[derive(Debug, Clone)]
+pub struct Paths<N, C> {
+    pub cost: HashMap<N, C>,
+    parent: HashMap<N, N>,
+}
+
+impl<N: Clone + Eq + Hash, C> Paths<N, C> {
+    /// Cheapest path from one of the search starts to `to`, both ends included
+    pub fn path(&self, to: &N) -> Option<Vec<N>> {
+        if !self.cost.contains_key(to) {
+            return None;
+        }
+
+        let mut path: Vec<_> = std::iter::successors(Some(to), |node| self.parent.get(node))
+            .cloned()
+            .collect();
+        path.reverse();
+
+        Some(path)
+    }
+}
+
+/// Breadth-first search starting from all the `starts` nodes at once
+///
+/// Returns the number of steps needed to reach every reachable node.
+pub fn bfs<N>(
+    starts: impl IntoIterator<Item = N>,
+    neighbors: impl Neighbors<N>,
+) -> HashMap<N, usize>
+where
+    N: Clone + Eq + Hash,
+{
+    bfs_paths(starts, neighbors).cost
+}
+
+/// Breadth-first search starting from all the `starts` nodes at once, keeping the found paths
+pub fn bfs_paths<N>(
+    starts: impl IntoIterator<Item = N>,
+    mut neighbors: impl Neighbors<N>,
+) -> Paths<N, usize>
+where
+    N: Clone + Eq + Hash,
+{
+    let mut distance = HashMap::new();
+    let mut parent = HashMap::new();
+    let mut queue = VecDeque::new();
+
+    for start in starts {
+        if distance.insert(start.clone(), 0).is_none() {
+            queue.push_back((start, 0));
+        }
+    }
+
+    while let Some((node, d)) = queue.pop_front() {
+        for next in neighbors.neighbors(&node) {
+            if !distance.contains_key(&next) {
+                distance.insert(next.clone(), d + 1);
+                parent.insert(next.clone(), node.clone());
+                queue.push_back((next, d + 1));
+            }
+        }
+    }
+
+    Paths {
+        cost: distance,
+        parent,
+    }
+}
+
+/// Dijkstra search starting from all the `starts` nodes at once
+///
+/// `neighbors` yields `(node, cost)` pairs. Returns the cost of the cheapest path to every
+/// reachable node.
+pub fn dijkstra<N>(
+    starts: impl IntoIterator<Item = N>,
+    neighbors: impl Neighbors<N, (N, u64)>,
+) -> HashMap<N, u64>
+where
+    N: Clone + Ord + Hash,
+{
+    dijkstra_paths(starts, neighbors).cost
+}
+
+/// Dijkstra search starting from all the `starts` nodes at once, keeping the cheapest paths
+pub fn dijkstra_paths<N>(
+    starts: impl IntoIterator<Item = N>,
+    mut neighbors: impl Neighbors<N, (N, u64)>,
+) -> Paths<N, u64>
+where
+    N: Clone + Ord + Hash,
+{
+    let mut distance = HashMap::new();
+    let mut parent = HashMap::new();
+    let mut queue = BinaryHeap::new();
+
+    for start in starts {
+        distance.insert(start.clone(), 0);
+        queue.push(Reverse((0, start)));
+    }
+
+    while let Some(Reverse((d, node))) = queue.pop() {
+        if distance.get(&node).is_some_and(|best| *best < d) {
+            continue;
+        }
+
+        for (next, cost) in neighbors.neighbors(&node) {
+            let d = d + cost;
+            if distance.get(&next).is_none_or(|best| *best > d) {
+                distance.insert(next.clone(), d);
+                parent.insert(next.clone(), node.clone());
+                queue.push(Reverse((d, next)));
+            }
+        }
+    }
+
+    Paths {
+        cost: distance,
+        parent,
+    }
+}
+
+/// A* search for the cheapest path from any of the `starts` to a node satisfying `is_goal`
+///
+/// `heuristic` has to never overestimate the remaining cost to the goal. Returns the cost of the
+/// path along with the path itself, both ends included.
+pub fn astar<N>(
+    starts: impl IntoIterator<Item = N>,
+    mut neighbors: impl Neighbors<N, (N, u64)>,
+    mut heuristic: impl FnMut(&N) -> u64,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Option<(u64, Vec<N>)>
+where
+    N: Clone + Ord + Hash,
+{
+    let mut paths = Paths {
+        cost: HashMap::new(),
+        parent: HashMap::new(),
+    };
+    let mut queue = BinaryHeap::new();
+
+    for start in starts {
+        paths.cost.insert(start.clone(), 0);
+        queue.push(Reverse((heuristic(&start), 0, start)));
+    }
+
+    while let Some(Reverse((_, d, node))) = queue.pop() {
+        if is_goal(&node) {
+            return Some((d, paths.path(&node)?));
+        }
+
+        if paths.cost.get(&node).is_some_and(|best| *best < d) {
+            continue;
+        }
+
+        for (next, cost) in neighbors.neighbors(&node) {
+            let d = d + cost;
+            if paths.cost.get(&next).is_none_or(|best| *best > d) {
+                paths.cost.insert(next.clone(), d);
+                paths.parent.insert(next.clone(), node.clone());
+                queue.push(Reverse((d + heuristic(&next), d, next)));
+            }
+        }
+    }
+
+    None
+}
+
+/// Floyd-Warshall cheapest paths between all pairs of `nodes` nodes indexed from `0`
+///
+/// `edges` are `(from, to, cost)` triples. The cost of the path from `a` to `b` is on `(a, b)`,
+/// `None` if there is no path.
+pub fn floyd_warshall(
+    nodes: usize,
+    edges: impl IntoIterator<Item = (usize, usize, u64)>,
+) -> Grid<Option<u64>> {
+    let mut dist = Grid::new(nodes, nodes, None);
+
+    for node in 0..nodes {
+        dist[(node, node)] = Some(0);
+    }
+
+    for (from, to, cost) in edges {
+        if dist[(from, to)].is_none_or(|best| best > cost) {
+            dist[(from, to)] = Some(cost);
+        }
+    }
+
+    for k in 0..nodes {
+        for a in 0..nodes {
+            let Some(ak) = dist[(a, k)] else {
+                continue;
+            };
+
+            for b in 0..nodes {
+                if let Some(kb) = dist[(k, b)] {
+                    if dist[(a, b)].is_none_or(|best| best > ak + kb) {
+                        dist[(a, b)] = Some(ak + kb);
+                    }
+                }
+            }
+        }
+    }
+
+    dist
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bfs_multi_source() {
+        // 0 - 1 - 2 - 3 - 4, searching from both ends
+        let dist = bfs([0, 4], |&n: &i32| {
+            [n - 1, n + 1].into_iter().filter(|n| (0..5).contains(n))
+        });
+
+        assert_eq!(dist[&2], 2);
+        assert_eq!(dist[&3], 1);
+        assert_eq!(dist.len(), 5);
+    }
+
+    #[test]
+    fn dijkstra_prefers_cheaper_path() {
+        let edges = |n: &char| match n {
+            'a' => vec![('b', 7), ('c', 2)],
+            'c' => vec![('b', 3)],
+            _ => vec![],
+        };
+
+        let dist = dijkstra(['a'], edges);
+        assert_eq!(dist[&'b'], 5);
+        assert_eq!(dist[&'c'], 2);
+
+        let paths = dijkstra_paths(['a'], edges);
+        assert_eq!(paths.path(&'b'), Some(vec!['a', 'c', 'b']));
+        assert_eq!(paths.path(&'d'), None);
+    }
+
+    #[test]
+    fn bfs_path_reconstruction() {
+        let paths = bfs_paths([0], |&n: &i32| {
+            [n + 1, n * 2].into_iter().filter(|n| *n <= 10)
+        });
+
+        assert_eq!(paths.cost[&10], 5);
+        assert_eq!(paths.path(&10), Some(vec![0, 1, 2, 4, 5, 10]));
+        assert_eq!(paths.path(&0), Some(vec![0]));
+    }
+
+    #[test]
+    fn astar_on_grid() {
+        // 5x5 grid with a wall in the middle column, open only at the bottom
+        let walls = [(2, 0), (2, 1), (2, 2), (2, 3)];
+        let grid = Grid::new(5, 5, ());
+        let neighbors = |p: &(usize, usize)| {
+            grid.neighbors4(*p)
+                .filter(|n| !walls.contains(n))
+                .map(|n| (n, 1))
+                .collect::<Vec<_>>()
+        };
+        let goal = (4, 0);
+        let heuristic = |&(x, y): &(usize, usize)| (x.abs_diff(goal.0) + y.abs_diff(goal.1)) as u64;
+
+        let (cost, path) = astar([(0, 0)], neighbors, heuristic, |p| *p == goal).unwrap();
+
+        assert_eq!(cost, 12);
+        assert_eq!(path.len(), 13);
+        assert_eq!(path.first(), Some(&(0, 0)));
+        assert_eq!(path.last(), Some(&goal));
+        assert!(path.contains(&(2, 4)));
+        assert_eq!(
+            astar([(0, 0)], neighbors, heuristic, |p| *p == (2, 0)),
+            None
+        );
+    }
+
+    #[test]
+    fn floyd_warshall_all_pairs() {
+        let dist = floyd_warshall(4, [(0, 1, 1), (1, 2, 2), (0, 2, 5), (2, 0, 1)]);
+
+        assert_eq!(dist[(0, 2)], Some(3));
+        assert_eq!(dist[(2, 1)], Some(2));
+        assert_eq!(dist[(1, 1)], Some(0));
+        assert_eq!(dist[(0, 3)], None);
+        assert_eq!(dist[(3, 0)], None);
+    }
+}
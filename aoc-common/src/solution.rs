@@ -0,0 +1,329 @@
+use std::fmt::Display;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+use anyhow::{bail, Result};
+use clap::{Parser, ValueEnum};
+use serde::Serialize;
+
+use crate::answers::{self, Verdict};
+use crate::{input, ParseError};
+
+/// Puzzle solution of a single day
+pub trait Solution {
+    /// Day of the advent calendar
+    const DAY: u8;
+
+    /// Parsed puzzle input, shared by both parts
+    type Input;
+
+    /// Parses the puzzle input, pointing to the offending place if it is malformed
+    fn parse(input: &str) -> Result<Self::Input, ParseError>;
+    fn part1(input: &Self::Input) -> Result<impl Display>;
+    fn part2(input: &Self::Input) -> Result<impl Display>;
+}
+
+/// Answers of a single day, `None` for parts which were not solved
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Answers {
+    pub part1: Option<String>,
+    pub part2: Option<String>,
+}
+
+impl Answers {
+    /// Solved parts with their answers
+    pub fn parts(&self) -> impl Iterator<Item = (u8, &str)> {
+        [(1, &self.part1), (2, &self.part2)]
+            .into_iter()
+            .filter_map(|(part, answer)| Some((part, answer.as_deref()?)))
+    }
+
+    fn get(&self, part: u8) -> Option<&str> {
+        match part {
+            1 => self.part1.as_deref(),
+            _ => self.part2.as_deref(),
+        }
+    }
+
+    /// Compares solved parts with the expected answers
+    pub fn verify(&self, expected: Option<&Answers>) -> Vec<(u8, Verdict)> {
+        self.parts()
+            .map(|(part, answer)| {
+                let expected = expected.and_then(|expected| expected.get(part));
+                (part, Verdict::new(answer, expected))
+            })
+            .collect()
+    }
+
+    pub fn print(&self) {
+        for (part, answer) in self.parts() {
+            print_answer(part, answer);
+        }
+    }
+
+    /// Number of solved parts not matching the expected answers
+    pub fn failures(&self, expected: Option<&Answers>) -> usize {
+        self.verify(expected)
+            .iter()
+            .filter(|(_, verdict)| verdict.is_fail())
+            .count()
+    }
+
+    /// Prints answers along with their verdicts, returns the number of mismatching parts
+    pub fn print_verified(&self, expected: Option<&Answers>) -> usize {
+        for ((part, answer), (_, verdict)) in self.parts().zip(self.verify(expected)) {
+            print_verified_answer(part, answer, &verdict);
+        }
+
+        self.failures(expected)
+    }
+}
+
+/// Prints a part answer, multi-line answers start on their own line
+pub fn print_answer(part: u8, answer: &str) {
+    if answer.contains('\n') {
+        println!("Part {part}:\n{answer}");
+    } else {
+        println!("Part {part}: {answer}");
+    }
+}
+
+/// Prints a part answer with its verdict, and the expected answer if it doesn't match
+pub fn print_verified_answer(part: u8, answer: &str, verdict: &Verdict) {
+    if answer.contains('\n') {
+        println!("Part {part} [{verdict}]:\n{answer}");
+    } else {
+        println!("Part {part}: {answer} [{verdict}]");
+    }
+
+    if let Verdict::Fail { expected } = verdict {
+        if expected.contains('\n') {
+            println!("Expected:\n{expected}");
+        } else {
+            println!("Expected: {expected}");
+        }
+    }
+}
+
+/// Answers of a single day along with the time spent on every solved part, parsing excluded
+#[derive(Debug, Default, Clone)]
+pub struct Solved {
+    pub answers: Answers,
+    pub part1: Option<Duration>,
+    pub part2: Option<Duration>,
+}
+
+/// Machine readable answer of a single part, the duration is in nanoseconds
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Record<'a> {
+    pub day: u8,
+    pub part: u8,
+    pub answer: &'a str,
+    pub duration: u64,
+}
+
+impl Solved {
+    /// Records of the solved parts of the day
+    pub fn records(&self, day: u8) -> Vec<Record<'_>> {
+        self.answers
+            .parts()
+            .map(|(part, answer)| {
+                let duration = match part {
+                    1 => self.part1,
+                    _ => self.part2,
+                };
+
+                Record {
+                    day,
+                    part,
+                    answer,
+                    duration: duration.unwrap_or_default().as_nanos() as u64,
+                }
+            })
+            .collect()
+    }
+
+    /// Prints records of the solved parts as JSON, one per line
+    pub fn print_json(&self, day: u8) -> Result<()> {
+        for record in self.records(day) {
+            println!("{}", serde_json::to_string(&record)?);
+        }
+
+        Ok(())
+    }
+
+    /// Solves both parts with the closures, timing each of them
+    ///
+    /// Meant for the day binaries solving the puzzle some other way than [`Solution`] does.
+    pub fn timed<T, U>(
+        part1: impl FnOnce() -> Result<T>,
+        part2: impl FnOnce() -> Result<U>,
+    ) -> Result<Solved>
+    where
+        T: Display,
+        U: Display,
+    {
+        let mut solved = Solved::default();
+        solved.solve_part(1, part1)?;
+        solved.solve_part(2, part2)?;
+
+        Ok(solved)
+    }
+
+    /// Solves the part with the closure, timing it
+    ///
+    /// The part is left unsolved if the closure fails, so the other one can still be reported.
+    pub fn solve_part<T: Display>(
+        &mut self,
+        part: u8,
+        f: impl FnOnce() -> Result<T>,
+    ) -> Result<()> {
+        let (answer, duration) = timed(f)?;
+
+        match part {
+            1 => (self.answers.part1, self.part1) = (Some(answer), Some(duration)),
+            _ => (self.answers.part2, self.part2) = (Some(answer), Some(duration)),
+        }
+
+        Ok(())
+    }
+
+    /// Prints the answers in the format, failing if any of them doesn't match the expected ones
+    ///
+    /// Answers are verified only against the given expected ones, there are none to give for an
+    /// input solved by rules other than the puzzle ones.
+    pub fn report(&self, day: u8, format: Format, expected: Option<&Answers>) -> Result<()> {
+        let failed = match (format, expected) {
+            (Format::Table, Some(expected)) => self.answers.print_verified(Some(expected)),
+            (format, expected) => {
+                match format {
+                    Format::Table => self.answers.print(),
+                    Format::Json => self.print_json(day)?,
+                }
+
+                self.answers.failures(expected)
+            }
+        };
+
+        if failed > 0 {
+            bail!("{failed} answer(s) do not match the expected ones");
+        }
+
+        Ok(())
+    }
+}
+
+fn timed<T>(f: impl FnOnce() -> Result<T>) -> Result<(String, Duration)>
+where
+    T: Display,
+{
+    let start = Instant::now();
+    let answer = f()?.to_string();
+    Ok((answer, start.elapsed()))
+}
+
+/// Parses the input and solves the given part, or both parts if none is given
+pub fn solve<S: Solution>(input: &str, part: Option<u8>) -> Result<Solved> {
+    solve_parsed::<S>(&S::parse(input)?, part)
+}
+
+fn solve_parsed<S: Solution>(input: &S::Input, part: Option<u8>) -> Result<Solved> {
+    let mut solved = Solved::default();
+
+    if part.is_none_or(|part| part == 1) {
+        solved.solve_part(1, || S::part1(input))?;
+    }
+
+    if part.is_none_or(|part| part == 2) {
+        solved.solve_part(2, || S::part2(input))?;
+    }
+
+    Ok(solved)
+}
+
+/// Output format of the answers
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    /// Human readable answers
+    Table,
+    /// JSON records, one per line
+    Json,
+}
+
+/// Arguments common for all the day binaries, to be flattened into the day specific ones
+#[derive(Debug, Clone, clap::Args)]
+pub struct Args {
+    /// Puzzle input file, `-` for stdin, `inputs/dayNN.txt` by default
+    pub input: Option<PathBuf>,
+
+    /// Output format, JSON answers are printed one part per line
+    #[arg(long, value_enum, default_value = "table")]
+    pub format: Format,
+}
+
+impl Args {
+    /// Path of the input file, `-` standing for stdin
+    pub fn path(&self, day: u8) -> PathBuf {
+        self.input
+            .clone()
+            .unwrap_or_else(|| input::default_path(day))
+    }
+}
+
+/// Solves both parts for the input given by the arguments, verifying the answers if the expected
+/// ones are known
+///
+/// Returns the parsed input, so the day binary can report anything else about it.
+pub fn run<S: Solution>(args: &Args) -> Result<S::Input> {
+    let path = args.path(S::DAY);
+    let input = S::parse(&input::read(&path)?)?;
+    let solved = solve_parsed::<S>(&input, None)?;
+    let expected = answers::load(&path)?;
+
+    solved.report(S::DAY, args.format, expected.as_ref())?;
+    Ok(input)
+}
+
+/// Entry point of a single day binary with no day specific arguments
+pub fn main<S: Solution>() -> Result<()> {
+    /// Advent of Code 2022 puzzle solution
+    #[derive(Parser)]
+    struct Cli {
+        #[command(flatten)]
+        args: Args,
+    }
+
+    run::<S>(&Cli::parse().args)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn records() {
+        let solved = Solved {
+            answers: Answers {
+                part1: None,
+                part2: Some("42".to_owned()),
+            },
+            part1: None,
+            part2: Some(Duration::from_micros(3)),
+        };
+
+        assert_eq!(
+            solved.records(7),
+            vec![Record {
+                day: 7,
+                part: 2,
+                answer: "42",
+                duration: 3000,
+            }]
+        );
+        assert_eq!(
+            serde_json::to_string(&solved.records(7)[0]).unwrap(),
+            r#"{"day":7,"part":2,"answer":"42","duration":3000}"#
+        );
+    }
+}
@@ -0,0 +1,46 @@
+use anyhow::Result;
+use aoc_common::bench::{bench, Timings};
+use aoc_common::solution::{solve, Solved};
+use aoc_common::Solution;
+
+/// Type erased solution of a single day
+pub struct Day {
+    pub day: u8,
+    pub solve: fn(&str, Option<u8>) -> Result<Solved>,
+    pub bench: fn(&str, usize) -> Result<Timings>,
+}
+
+impl Day {
+    const fn new<S: Solution>() -> Self {
+        Self {
+            day: S::DAY,
+            solve: solve::<S>,
+            bench: bench::<S>,
+        }
+    }
+}
+
+/// All the solved days, in order
+pub const DAYS: [Day; 21] = [
+    Day::new::<day1::Day1>(),
+    Day::new::<day2::Day2>(),
+    Day::new::<day3::Day3>(),
+    Day::new::<day4::Day4>(),
+    Day::new::<day5::Day5>(),
+    Day::new::<day6::Day6>(),
+    Day::new::<day7::Day7>(),
+    Day::new::<day8::Day8>(),
+    Day::new::<day9::Day9>(),
+    Day::new::<day10::Day10>(),
+    Day::new::<day11::Day11>(),
+    Day::new::<day12::Day12>(),
+    Day::new::<day13::Day13>(),
+    Day::new::<day14::Day14>(),
+    Day::new::<day15::Day15>(),
+    Day::new::<day16::Day16>(),
+    Day::new::<day17::Day17>(),
+    Day::new::<day18::Day18>(),
+    Day::new::<day19::Day19>(),
+    Day::new::<day20::Day20>(),
+    Day::new::<day21::Day21>(),
+];
@@ -0,0 +1,128 @@
+use std::path::PathBuf;
+
+use anyhow::{anyhow, bail, Result};
+use aoc_common::answers;
+use aoc_common::bench::Timings;
+use aoc_common::solution::Format;
+use clap::{Args, Parser, Subcommand};
+
+mod days;
+mod report;
+
+use days::{Day, DAYS};
+
+/// Advent of Code 2022 solutions
+#[derive(Parser)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Solves a single day, or all of them
+    Run(RunArgs),
+}
+
+#[derive(Args)]
+struct RunArgs {
+    /// Day to solve
+    #[arg(long, required_unless_present = "all")]
+    day: Option<u8>,
+
+    /// Part to solve, both if not given
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+    part: Option<u8>,
+
+    /// Puzzle input file, `-` for stdin, `inputs/dayNN.txt` by default
+    #[arg(long, conflicts_with = "all")]
+    input: Option<PathBuf>,
+
+    /// Solves all the days in order
+    #[arg(long, conflicts_with = "day")]
+    all: bool,
+
+    /// Instead of printing answers, measures parsing and both parts over N iterations
+    #[arg(long, value_name = "N", conflicts_with = "part", value_parser = clap::value_parser!(u64).range(1..))]
+    bench: Option<u64>,
+
+    /// Output format of answers or benchmark results, JSON answers are printed one part per line
+    #[arg(long, value_enum, default_value = "table")]
+    format: Format,
+}
+
+fn input_path(day: &Day, input: Option<PathBuf>) -> PathBuf {
+    input.unwrap_or_else(|| aoc_common::input::default_path(day.day))
+}
+
+/// Solves the day and verifies the answers, returns the number of mismatching parts
+fn run_day(day: &Day, input: Option<PathBuf>, part: Option<u8>, format: Format) -> Result<usize> {
+    let path = input_path(day, input);
+    let input = aoc_common::input::read(&path)?;
+    let expected = answers::load(&path)?;
+    let solved = (day.solve)(&input, part)?;
+
+    let failed = match format {
+        Format::Table => {
+            println!("Day {}", day.day);
+            solved.answers.print_verified(expected.as_ref())
+        }
+        Format::Json => {
+            solved.print_json(day.day)?;
+            solved.answers.failures(expected.as_ref())
+        }
+    };
+
+    Ok(failed)
+}
+
+fn bench_day(day: &Day, input: Option<PathBuf>, iterations: u64) -> Result<(u8, Timings)> {
+    let input = aoc_common::input::read(&input_path(day, input))?;
+    let timings = (day.bench)(&input, iterations as usize)?;
+
+    Ok((day.day, timings))
+}
+
+fn run(args: RunArgs) -> Result<()> {
+    let days: Vec<_> = match args.day {
+        Some(day) => {
+            let day = DAYS
+                .iter()
+                .find(|d| d.day == day)
+                .ok_or_else(|| anyhow!("Day {day} is not solved"))?;
+            vec![day]
+        }
+        None => DAYS.iter().collect(),
+    };
+
+    let Some(iterations) = args.bench else {
+        let mut failed = 0;
+        for day in days {
+            failed += run_day(day, args.input.clone(), args.part, args.format)?;
+        }
+
+        if failed > 0 {
+            bail!("{failed} answer(s) do not match the expected ones");
+        }
+
+        return Ok(());
+    };
+
+    let timings = days
+        .into_iter()
+        .map(|day| bench_day(day, args.input.clone(), iterations))
+        .collect::<Result<Vec<_>>>()?;
+
+    match args.format {
+        Format::Table => report::print_table(&timings),
+        Format::Json => report::print_json(iterations, &timings)?,
+    }
+
+    Ok(())
+}
+
+fn main() -> Result<()> {
+    match Cli::parse().command {
+        Command::Run(args) => run(args),
+    }
+}
@@ -0,0 +1,47 @@
+use anyhow::Result;
+use aoc_common::bench::Timings;
+use serde_json::json;
+
+/// Prints benchmark results as a human readable table
+pub fn print_table(timings: &[(u8, Timings)]) {
+    println!(
+        "{:>3}  {:<5}  {:>12}  {:>12}  {:>12}",
+        "Day", "Stage", "Min", "Median", "Max"
+    );
+
+    for (day, timings) in timings {
+        for (stage, stats) in timings.stages() {
+            println!(
+                "{day:>3}  {stage:<5}  {:>12}  {:>12}  {:>12}",
+                format!("{:.1?}", stats.min),
+                format!("{:.1?}", stats.median),
+                format!("{:.1?}", stats.max),
+            );
+        }
+    }
+}
+
+/// Prints benchmark results as JSON, all durations are in nanoseconds
+pub fn print_json(iterations: u64, timings: &[(u8, Timings)]) -> Result<()> {
+    let days: Vec<_> = timings
+        .iter()
+        .map(|(day, timings)| {
+            let mut record = json!({ "day": day });
+
+            for (stage, stats) in timings.stages() {
+                record[stage] = json!({
+                    "min": stats.min.as_nanos() as u64,
+                    "median": stats.median.as_nanos() as u64,
+                    "max": stats.max.as_nanos() as u64,
+                });
+            }
+
+            record
+        })
+        .collect();
+
+    let report = json!({ "iterations": iterations, "days": days });
+    println!("{}", serde_json::to_string_pretty(&report)?);
+
+    Ok(())
+}
@@ -0,0 +1,415 @@
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::fmt::Display;
+use std::io::BufRead;
+
+use anyhow::Result;
+use aoc_common::{ParseError, Solution, Source};
+
+/// Incremental parser of the elves, fed with the input line by line
+///
+/// Elves are separated by blank lines, any number of them. If comments are allowed, they start with
+/// `#` and run to the end of the line, lines with just a comment are skipped. Otherwise a `#` is
+/// rejected as any other character which is not a part of a number.
+#[derive(Debug, Clone)]
+struct Elves {
+    /// Whether `#` comments are skipped
+    comments: bool,
+    /// Number of the last line pushed
+    line: usize,
+    /// Line and column right after the input pushed so far
+    end: (usize, usize),
+    /// Number of elves visited so far
+    count: usize,
+    /// Calories carried by the elf being parsed
+    elf: Option<u128>,
+}
+
+impl Elves {
+    fn new(comments: bool) -> Self {
+        Self {
+            comments,
+            line: 0,
+            end: (1, 1),
+            count: 0,
+            elf: None,
+        }
+    }
+
+    /// Parses the line, with or without its line break, visiting the elf it ends
+    fn push(&mut self, line: &str, visit: &mut impl FnMut(usize, u128)) -> Result<(), ParseError> {
+        self.line += 1;
+        self.end = match line.ends_with('\n') {
+            true => (self.line + 1, 1),
+            false => (self.line, line.chars().count() + 1),
+        };
+
+        if line.trim().is_empty() {
+            self.close(visit);
+            return Ok(());
+        }
+
+        let item = match self.comments {
+            true => line.split('#').next().unwrap_or_default().trim(),
+            false => line.trim(),
+        };
+        if !item.is_empty() {
+            let cals: u128 = Source::new(line)
+                .number(item)
+                .map_err(|err| ParseError::new(self.line, err.column, err.expected))?;
+            *self.elf.get_or_insert(0) += cals;
+        }
+
+        Ok(())
+    }
+
+    fn close(&mut self, visit: &mut impl FnMut(usize, u128)) {
+        if let Some(calories) = self.elf.take() {
+            visit(self.count, calories);
+            self.count += 1;
+        }
+    }
+
+    /// Visits the last elf, failing if there were none at all
+    fn finish(mut self, visit: &mut impl FnMut(usize, u128)) -> Result<(), ParseError> {
+        self.close(visit);
+
+        if self.count == 0 {
+            let (line, column) = self.end;
+            return Err(ParseError::new(
+                line,
+                column,
+                "calories of at least one elf",
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+/// Visits the calories carried by every elf with the elf index, in the order of the input
+fn elves(
+    input: &str,
+    comments: bool,
+    mut visit: impl FnMut(usize, u128),
+) -> Result<(), ParseError> {
+    let mut elves = Elves::new(comments);
+    for line in input.split_inclusive('\n') {
+        elves.push(line, &mut visit)?;
+    }
+
+    elves.finish(&mut visit)
+}
+
+/// Same as visiting the elves of the input text, for the input read line by line
+///
+/// Only the line being parsed is kept in memory.
+pub fn read_elves(
+    mut reader: impl BufRead,
+    comments: bool,
+    mut visit: impl FnMut(usize, u128),
+) -> Result<()> {
+    let mut elves = Elves::new(comments);
+    let mut line = String::new();
+
+    while reader.read_line(&mut line)? > 0 {
+        elves.push(&line, &mut visit)?;
+        line.clear();
+    }
+
+    Ok(elves.finish(&mut visit)?)
+}
+
+/// Calories carried by every elf, in the order of the input
+pub fn totals(input: &str, comments: bool) -> Result<Vec<u128>, ParseError> {
+    let mut totals = vec![];
+    elves(input, comments, |_, calories| totals.push(calories))?;
+    Ok(totals)
+}
+
+/// Streaming aggregator of the `k` elves carrying the most calories
+///
+/// Only the current top is kept in a min-heap, so every pushed elf costs `O(log k)`. Elves carrying
+/// the same amount are ordered by their index.
+#[derive(Debug, Clone)]
+pub struct TopK {
+    k: usize,
+    heap: BinaryHeap<Reverse<(u128, Reverse<usize>)>>,
+}
+
+impl TopK {
+    pub fn new(k: usize) -> Self {
+        Self {
+            k,
+            heap: BinaryHeap::with_capacity(k + 1),
+        }
+    }
+
+    pub fn push(&mut self, elf: usize, calories: u128) {
+        let entry = Reverse((calories, Reverse(elf)));
+
+        if self.heap.len() < self.k {
+            self.heap.push(entry);
+        } else if self.heap.peek().is_some_and(|min| entry < *min) {
+            self.heap.pop();
+            self.heap.push(entry);
+        }
+    }
+
+    /// Top elves with their calories, the most loaded first
+    pub fn into_sorted(self) -> Vec<(usize, u128)> {
+        self.heap
+            .into_sorted_vec()
+            .into_iter()
+            .map(|Reverse((calories, Reverse(elf)))| (elf, calories))
+            .collect()
+    }
+}
+
+/// The `k` elves carrying the most calories as `(elf index, calories)`, the most loaded first
+///
+/// Elves are pushed to the top as they are parsed, their calories are not collected.
+pub fn top_elves(input: &str, k: usize, comments: bool) -> Result<Vec<(usize, u128)>, ParseError> {
+    let mut top = TopK::new(k);
+    elves(input, comments, |elf, calories| top.push(elf, calories))?;
+    Ok(top.into_sorted())
+}
+
+/// Number of ranges in the calories distribution
+const BUCKETS: usize = 10;
+
+/// Statistics of calories carried by the elves
+#[derive(Debug, Clone, PartialEq)]
+pub struct Summary {
+    pub count: usize,
+    pub mean: f64,
+    pub median: f64,
+    pub min: u128,
+    pub max: u128,
+    /// Number of elves in every of equally wide ranges between `min` and `max`, as
+    /// `(range start, range end, elves)`, both ends included
+    pub distribution: Vec<(u128, u128, usize)>,
+}
+
+impl Summary {
+    /// Summarizes non-empty elves calories
+    pub fn new(elves: &[u128]) -> Self {
+        let mut sorted = elves.to_vec();
+        sorted.sort_unstable();
+
+        let count = sorted.len();
+        let (min, max) = (sorted[0], sorted[count - 1]);
+        let median = match count % 2 {
+            0 => (sorted[count / 2 - 1] + sorted[count / 2]) as f64 / 2.,
+            _ => sorted[count / 2] as f64,
+        };
+
+        // The last range is closed by the max, so it is up to `width` wider
+        let width = (max - min).div_ceil(BUCKETS as u128).max(1);
+        let mut distribution: Vec<_> = (0..BUCKETS as u128)
+            .map(|bucket| min + bucket * width)
+            .take_while(|start| *start <= max)
+            .map(|start| (start, start + width - 1, 0))
+            .collect();
+
+        let last = distribution.len() - 1;
+        distribution[last].1 = max;
+
+        for calories in &sorted {
+            let bucket = (((calories - min) / width) as usize).min(last);
+            distribution[bucket].2 += 1;
+        }
+
+        Self {
+            count,
+            mean: sorted.iter().sum::<u128>() as f64 / count as f64,
+            median,
+            min,
+            max,
+            distribution,
+        }
+    }
+}
+
+impl Display for Summary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        const BAR: usize = 40;
+
+        writeln!(f, "Elves: {}", self.count)?;
+        writeln!(f, "Mean: {:.1}", self.mean)?;
+        writeln!(f, "Median: {:.1}", self.median)?;
+        writeln!(f, "Min: {}, max: {}", self.min, self.max)?;
+        writeln!(f, "Distribution:")?;
+
+        let most = self.distribution.iter().map(|(_, _, elves)| *elves).max();
+        for (start, end, elves) in &self.distribution {
+            let bar = elves * BAR / most.unwrap_or(1).max(1);
+            let line = format!("  {start:>8} - {end:<8} {elves:>6} {}", "#".repeat(bar));
+            writeln!(f, "{}", line.trim_end())?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Number of the most loaded elves the answers are computed from
+pub const TOP: usize = 3;
+
+fn part1(top: &[(usize, u128)]) -> u128 {
+    top[0].1
+}
+
+fn part2(top: &[(usize, u128)]) -> u128 {
+    top.iter().map(|(_, calories)| calories).sum()
+}
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    const DAY: u8 = 1;
+
+    /// The elves carrying the most calories, the most loaded first
+    type Input = Vec<(usize, u128)>;
+
+    /// Comments are not allowed, the puzzle input has none
+    fn parse(data: &str) -> Result<Self::Input, ParseError> {
+        top_elves(data, TOP, false)
+    }
+
+    fn part1(input: &Self::Input) -> Result<impl Display> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input) -> Result<impl Display> {
+        Ok(part2(input))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../test.in");
+
+    #[test]
+    fn input_test() {
+        assert_eq!(
+            totals(EXAMPLE, false).unwrap(),
+            vec![6000, 4000, 11000, 24000, 10000]
+        );
+    }
+
+    #[test]
+    fn separators_and_comments() {
+        let data = "\n# Inventory\n1000\n2000 # snacks\n\n\n\n3000\n  # nothing more\n\n\n";
+
+        assert_eq!(totals(data, true).unwrap(), vec![3000, 3000]);
+
+        // Comments are rejected unless allowed
+        assert_eq!(totals(data, false), Err(ParseError::new(2, 1, "number")));
+        assert_eq!(
+            top_elves("1000 # snacks\n", 1, false),
+            Err(ParseError::new(1, 1, "number"))
+        );
+    }
+
+    #[test]
+    fn invalid_input() {
+        assert_eq!(
+            totals("1000\n\n20x0\n", false),
+            Err(ParseError::new(3, 1, "number"))
+        );
+        assert_eq!(
+            top_elves("1000\n 300 # ok\n1 2\n", 1, true),
+            Err(ParseError::new(3, 1, "number"))
+        );
+        assert_eq!(
+            totals("\n# nobody\n\n", true),
+            Err(ParseError::new(4, 1, "calories of at least one elf"))
+        );
+        assert_eq!(
+            Day1::parse("\n# nobody\n\n"),
+            Err(ParseError::new(2, 1, "number"))
+        );
+        assert_eq!(
+            Day1::parse(""),
+            Err(ParseError::new(1, 1, "calories of at least one elf"))
+        );
+    }
+
+    #[test]
+    fn read_lines() {
+        let data = "1000\n2000 # snacks\n\n\n3000\n";
+        let mut totals = vec![];
+        read_elves(data.as_bytes(), true, |elf, calories| {
+            totals.push((elf, calories))
+        })
+        .unwrap();
+        assert_eq!(totals, vec![(0, 3000), (1, 3000)]);
+
+        let error = |data: &str| {
+            read_elves(data.as_bytes(), true, |_, _| {})
+                .unwrap_err()
+                .downcast::<ParseError>()
+                .unwrap()
+        };
+        assert_eq!(error("1000\n\n 20x0\n"), ParseError::new(3, 2, "number"));
+        assert_eq!(
+            error("\n# nobody\n\n"),
+            ParseError::new(4, 1, "calories of at least one elf")
+        );
+        assert_eq!(
+            error("# nobody"),
+            ParseError::new(1, 9, "calories of at least one elf")
+        );
+    }
+
+    #[test]
+    fn summary_test() {
+        let summary = Summary::new(&totals(EXAMPLE, false).unwrap());
+
+        assert_eq!(summary.count, 5);
+        assert_eq!(summary.mean, 11000.);
+        assert_eq!(summary.median, 10000.);
+        assert_eq!((summary.min, summary.max), (4000, 24000));
+        assert_eq!(summary.distribution.len(), 10);
+        assert_eq!(summary.distribution[0], (4000, 5999, 1));
+        assert_eq!(summary.distribution[9], (22000, 24000, 1));
+        assert_eq!(
+            summary
+                .distribution
+                .iter()
+                .map(|(_, _, elves)| elves)
+                .sum::<usize>(),
+            5
+        );
+
+        let summary = Summary::new(&[7, 7]);
+        assert_eq!(summary.median, 7.);
+        assert_eq!(summary.distribution, vec![(7, 7, 2)]);
+    }
+
+    #[test]
+    fn top_elves_test() {
+        assert_eq!(
+            top_elves(EXAMPLE, 2, false).unwrap(),
+            vec![(3, 24000), (2, 11000)]
+        );
+        assert_eq!(top_elves(EXAMPLE, 0, false).unwrap(), vec![]);
+        assert_eq!(top_elves(EXAMPLE, 10, false).unwrap().len(), 5);
+        assert_eq!(
+            top_elves("5\n\n7\n\n5\n\n7\n\n1", 3, false).unwrap(),
+            vec![(1, 7), (3, 7), (0, 5)]
+        );
+    }
+
+    #[test]
+    fn part1_test() {
+        assert_eq!(part1(&Day1::parse(EXAMPLE).unwrap()), 24000);
+    }
+
+    #[test]
+    fn part2_test() {
+        assert_eq!(part2(&Day1::parse(EXAMPLE).unwrap()), 45000);
+    }
+}
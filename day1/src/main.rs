@@ -1,42 +1,118 @@
-use anyhow::Result;
-use std::io::BufRead;
-
-fn input() -> Result<Vec<u128>> {
-    let (mut max, buf) =
-        std::io::stdin()
-            .lock()
-            .lines()
-            .fold(Ok((vec![], 0)), |buf, line| -> Result<_> {
-                let (mut callories, buf) = buf?;
-                let line = line?;
-
-                if line.is_empty() {
-                    callories.push(buf);
-                    Ok((callories, 0))
-                } else {
-                    let cals: u128 = line.parse().unwrap();
-                    Ok((callories, buf + cals))
-                }
-            })?;
-
-    max.push(buf);
-    max.sort();
-    Ok(max)
+use std::io::BufReader;
+
+use aoc_common::solution::{Args, Format, Solved};
+use aoc_common::Solution;
+use clap::Parser;
+use day1::{read_elves, Day1, Summary, TopK, TOP};
+use serde_json::json;
+
+/// Calorie counting
+#[derive(Parser)]
+struct Cli {
+    #[command(flatten)]
+    args: Args,
+
+    /// Also reports the K elves carrying the most calories
+    #[arg(long, value_name = "K")]
+    top: Option<usize>,
+
+    /// Also reports statistics of the calories carried by the elves
+    #[arg(long)]
+    summary: bool,
+
+    /// Skips comments, starting with `#` and running to the end of the line
+    #[arg(long)]
+    comments: bool,
 }
 
-fn part1(input: &[u128]) -> u128 {
-    *input.iter().last().unwrap()
+fn print_top(top: &[(usize, u128)], k: usize, format: Format) {
+    // Elves are reported by their position in the input, counting from 1
+    let top: Vec<_> = top
+        .iter()
+        .take(k)
+        .map(|(elf, calories)| (elf + 1, *calories))
+        .collect();
+
+    match format {
+        Format::Table => {
+            let total: u128 = top.iter().map(|(_, calories)| calories).sum();
+
+            println!("Top {} elves, carrying {total} calories:", top.len());
+            for (elf, calories) in top {
+                println!("  elf {elf}: {calories}");
+            }
+        }
+        Format::Json => {
+            let top: Vec<_> = top
+                .into_iter()
+                .map(|(elf, calories)| json!({ "elf": elf, "calories": calories }))
+                .collect();
+
+            println!("{}", json!({ "day": 1, "top": top }));
+        }
+    }
 }
 
-fn part2(input: &[u128]) -> u128 {
-    input.iter().rev().take(3).sum()
+fn print_summary(totals: &[u128], format: Format) {
+    let summary = Summary::new(totals);
+
+    match format {
+        Format::Table => print!("{summary}"),
+        Format::Json => {
+            let distribution: Vec<_> = summary
+                .distribution
+                .iter()
+                .map(|(start, end, elves)| json!({ "start": start, "end": end, "elves": elves }))
+                .collect();
+
+            let summary = json!({
+                "count": summary.count,
+                "mean": summary.mean,
+                "median": summary.median,
+                "min": summary.min,
+                "max": summary.max,
+                "distribution": distribution,
+            });
+
+            println!("{}", json!({ "day": 1, "summary": summary }));
+        }
+    }
 }
 
-fn main() -> Result<()> {
-    let input = input()?;
+fn main() -> anyhow::Result<()> {
+    let cli = Cli::parse();
+    let path = cli.args.path(Day1::DAY);
+
+    // The input is parsed once as it is read, the top is kept large enough for the answers too
+    let mut top = TopK::new(cli.top.unwrap_or(0).max(TOP));
+    let mut totals = vec![];
+    read_elves(
+        BufReader::new(aoc_common::input::open(&path)?),
+        cli.comments,
+        |elf, calories| {
+            top.push(elf, calories);
+            if cli.summary {
+                totals.push(calories);
+            }
+        },
+    )?;
+
+    let top = top.into_sorted();
+    let leaders: Vec<_> = top.iter().copied().take(TOP).collect();
+    let expected = aoc_common::answers::load(&path)?;
+    Solved::timed(|| Day1::part1(&leaders), || Day1::part2(&leaders))?.report(
+        Day1::DAY,
+        cli.args.format,
+        expected.as_ref(),
+    )?;
+
+    if let Some(k) = cli.top {
+        print_top(&top, k, cli.args.format);
+    }
 
-    println!("Part 1: {}", part1(&input));
-    println!("Part 2: {}", part2(&input));
+    if cli.summary {
+        print_summary(&totals, cli.args.format);
+    }
 
     Ok(())
 }
@@ -0,0 +1,138 @@
+use std::fmt::Display;
+
+use anyhow::Result;
+use aoc_common::{ParseError, Solution, Source};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Instruction {
+    Addx(i128),
+    Noop,
+}
+
+fn input(input: &str) -> Result<Vec<Instruction>, ParseError> {
+    let source = Source::new(input);
+
+    aoc_common::input::non_empty_lines(input)
+        .map(|line| match line.trim_end() {
+            "noop" => Ok(Instruction::Noop),
+            line => match line.strip_prefix("addx ") {
+                Some(v) => Ok(Instruction::Addx(source.number(v)?)),
+                None => Err(source.error(line, "`noop` or `addx` instruction")),
+            },
+        })
+        .collect()
+}
+
+fn execute(program: &[Instruction]) -> impl Iterator<Item = i128> + '_ {
+    use Instruction::*;
+
+    program
+        .iter()
+        .scan(1, |x, instr| match instr {
+            Addx(v) => {
+                let old = *x;
+                *x += v;
+                Some(vec![old; 2])
+            }
+            Noop => Some(vec![*x]),
+        })
+        .flatten()
+}
+
+fn part1(program: &[Instruction]) -> i128 {
+    execute(program)
+        .enumerate()
+        .skip(19)
+        .step_by(40)
+        .take(6)
+        .map(|(i, x)| ((i + 1) as i128) * x)
+        .sum()
+}
+
+fn part2(program: &[Instruction]) -> Vec<String> {
+    let display: Vec<_> = execute(program)
+        .enumerate()
+        .map(|(i, x)| match ((i % 40) as i128 - x).abs() <= 1 {
+            true => '#',
+            false => '.',
+        })
+        .collect();
+
+    display
+        .chunks(40)
+        .map(|line| line.iter().collect())
+        .collect()
+}
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    const DAY: u8 = 10;
+
+    type Input = Vec<Instruction>;
+
+    fn parse(data: &str) -> Result<Self::Input, ParseError> {
+        input(data)
+    }
+
+    fn part1(input: &Self::Input) -> Result<impl Display> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input) -> Result<impl Display> {
+        Ok(part2(input).join("\n"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../test.in");
+
+    #[test]
+    fn input_test() {
+        use Instruction::*;
+
+        assert_eq!(
+            input("noop\naddx 3\naddx -5").unwrap(),
+            vec![Noop, Addx(3), Addx(-5)]
+        );
+        assert_eq!(input(EXAMPLE).unwrap().len(), 146);
+    }
+
+    #[test]
+    fn invalid_input() {
+        assert_eq!(
+            input("noop\nadd 3"),
+            Err(ParseError::new(2, 1, "`noop` or `addx` instruction"))
+        );
+        assert_eq!(input("addx x"), Err(ParseError::new(1, 6, "number")));
+    }
+
+    #[test]
+    fn execute_test() {
+        let program = input("noop\naddx 3\naddx -5").unwrap();
+        assert_eq!(execute(&program).collect::<Vec<_>>(), vec![1, 1, 1, 4, 4]);
+    }
+
+    #[test]
+    fn part1_test() {
+        assert_eq!(part1(&input(EXAMPLE).unwrap()), 13140);
+    }
+
+    #[test]
+    fn part2_test() {
+        assert_eq!(
+            part2(&input(EXAMPLE).unwrap()),
+            vec![
+                "##..##..##..##..##..##..##..##..##..##..",
+                "###...###...###...###...###...###...###.",
+                "####....####....####....####....####....",
+                "#####.....#####.....#####.....#####.....",
+                "######......######......######......####",
+                "#######.......#######.......#######.....",
+            ]
+        );
+    }
+}
@@ -0,0 +1,291 @@
+use std::fmt::Display;
+
+use anyhow::Result;
+use aoc_common::{ParseError, Solution, Source};
+use derivative::Derivative;
+
+type OpFn = dyn Fn(u128) -> u128;
+
+#[derive(Derivative)]
+#[derivative(Debug)]
+pub struct MonkeyDesc {
+    #[derivative(Debug = "ignore")]
+    op: Box<OpFn>,
+    test: u128,
+    tbranch: usize,
+    fbranch: usize,
+}
+
+#[derive(Debug, Clone)]
+pub struct MonkeyState {
+    items: Vec<u128>,
+    inspections: usize,
+}
+
+fn parse_starting_items(source: &Source, line: &str) -> Result<Vec<u128>, ParseError> {
+    let items = source.strip_prefix(line, "Starting items:")?.trim();
+
+    if items.is_empty() {
+        return Ok(vec![]);
+    }
+
+    items
+        .split(',')
+        .map(|item| source.number(item.trim()))
+        .collect()
+}
+
+fn parse_op(source: &Source, line: &str) -> Result<Box<OpFn>, ParseError> {
+    let op = source.strip_prefix(line, "Operation: new = old ")?;
+    let (op, val) = source.split_once(op, " ")?;
+
+    let res = match (op, val) {
+        ("+", "old") => Box::new(|x| x * 2u128) as Box<OpFn>,
+        ("*", "old") => Box::new(|x| x * x),
+        ("+", val) => {
+            let val: u128 = source.number(val)?;
+            Box::new(move |x| x + val)
+        }
+        ("*", val) => {
+            let val: u128 = source.number(val)?;
+            Box::new(move |x| x * val)
+        }
+        _ => return Err(source.error(op, "operator `+` or `*`")),
+    };
+
+    Ok(res)
+}
+
+fn parse_test(source: &Source, line: &str) -> Result<u128, ParseError> {
+    let test = source.strip_prefix(line, "Test: divisible by ")?;
+    let test = source.number(test)?;
+
+    if test == 0 {
+        return Err(source.error(line, "non-zero divisor"));
+    }
+
+    Ok(test)
+}
+
+fn parse_branch<'a>(
+    source: &Source<'a>,
+    line: &'a str,
+    branch: &str,
+) -> Result<&'a str, ParseError> {
+    source.strip_prefix(line, &format!("If {branch}: throw to monkey "))
+}
+
+fn input(input: &str) -> Result<(Vec<MonkeyDesc>, Vec<MonkeyState>), ParseError> {
+    let source = Source::new(input);
+    let mut lines = aoc_common::input::non_empty_lines(input).map(str::trim);
+    let mut monkeys = vec![];
+
+    while let Some(header) = lines.next() {
+        let idx = source.strip_prefix(header, "Monkey ")?;
+        let idx = source.split_once(idx, ":")?.0;
+        if source.number::<usize>(idx)? != monkeys.len() {
+            return Err(source.error(idx, format!("monkey {}", monkeys.len())));
+        }
+
+        let mut next = |expected| {
+            lines
+                .next()
+                .ok_or_else(|| source.error_after(input, expected))
+        };
+
+        let items = parse_starting_items(&source, next("`Starting items:`")?)?;
+        let op = parse_op(&source, next("`Operation:`")?)?;
+        let test = parse_test(&source, next("`Test:`")?)?;
+        let tbranch = parse_branch(&source, next("`If true:`")?, "true")?;
+        let fbranch = parse_branch(&source, next("`If false:`")?, "false")?;
+
+        let state = MonkeyState {
+            items,
+            inspections: 0,
+        };
+
+        monkeys.push((op, test, tbranch, fbranch, state));
+    }
+
+    // Branches can refer monkeys defined later, so they are verified when all are known
+    let count = monkeys.len();
+    let branch = |target: &str| -> Result<usize, ParseError> {
+        match source.number(target)? {
+            target if target < count => Ok(target),
+            _ => Err(source.error(target, "existing monkey")),
+        }
+    };
+
+    let mut descs = Vec::with_capacity(monkeys.len());
+    let mut states = Vec::with_capacity(monkeys.len());
+
+    for (op, test, tbranch, fbranch, state) in monkeys {
+        descs.push(MonkeyDesc {
+            op,
+            test,
+            tbranch: branch(tbranch)?,
+            fbranch: branch(fbranch)?,
+        });
+        states.push(state);
+    }
+
+    // Monkey business is made by the two most active monkeys
+    if descs.len() < 2 {
+        return Err(source.error_after(input, format!("monkey {}", descs.len())));
+    }
+
+    Ok((descs, states))
+}
+
+fn part1(descs: &[MonkeyDesc], states: Vec<MonkeyState>) -> usize {
+    let mut inspections: Vec<_> = std::iter::successors(Some(states), |prev| {
+        let mut state = prev.clone();
+
+        for idx in 0..prev.len() {
+            let monkey = &mut state[idx];
+            let desc = &descs[idx];
+
+            monkey.inspections += monkey.items.len();
+
+            for item in &mut monkey.items {
+                *item = (descs[idx].op)(*item) / 3;
+            }
+
+            let (t, f): (Vec<_>, Vec<_>) =
+                monkey.items.drain(..).partition(|x| *x % desc.test == 0);
+
+            state[desc.tbranch].items.extend(t);
+            state[desc.fbranch].items.extend(f);
+        }
+
+        Some(state)
+    })
+    .nth(20)
+    .unwrap_or_default()
+    .into_iter()
+    .map(|s| s.inspections)
+    .collect();
+
+    inspections.select_nth_unstable_by(1, |a, b| b.cmp(a));
+
+    inspections[..2].iter().product()
+}
+
+fn part2(descs: &[MonkeyDesc], states: Vec<MonkeyState>) -> usize {
+    let m = descs.iter().map(|d| d.test).product::<u128>();
+
+    let mut inspections: Vec<_> = std::iter::successors(Some(states), |prev| {
+        let mut state = prev.clone();
+
+        for idx in 0..prev.len() {
+            let monkey = &mut state[idx];
+            let desc = &descs[idx];
+
+            monkey.inspections += monkey.items.len();
+
+            for item in &mut monkey.items {
+                *item = (descs[idx].op)(*item) % m;
+            }
+
+            let (t, f): (Vec<_>, Vec<_>) =
+                monkey.items.drain(..).partition(|x| *x % desc.test == 0);
+
+            state[desc.tbranch].items.extend(t);
+            state[desc.fbranch].items.extend(f);
+        }
+
+        Some(state)
+    })
+    .nth(10000)
+    .unwrap_or_default()
+    .into_iter()
+    .map(|s| s.inspections)
+    .collect();
+
+    inspections.select_nth_unstable_by(1, |a, b| b.cmp(a));
+
+    inspections[..2].iter().product()
+}
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    const DAY: u8 = 11;
+
+    type Input = (Vec<MonkeyDesc>, Vec<MonkeyState>);
+
+    fn parse(data: &str) -> Result<Self::Input, ParseError> {
+        input(data)
+    }
+
+    fn part1(input: &Self::Input) -> Result<impl Display> {
+        let (descs, states) = input;
+        Ok(part1(descs, states.clone()))
+    }
+
+    fn part2(input: &Self::Input) -> Result<impl Display> {
+        let (descs, states) = input;
+        Ok(part2(descs, states.clone()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../test.in");
+
+    #[test]
+    fn input_test() {
+        let (descs, states) = input(EXAMPLE).unwrap();
+
+        assert_eq!(descs.len(), 4);
+        assert_eq!(states.len(), 4);
+
+        assert_eq!(states[0].items, vec![79, 98]);
+        assert_eq!((descs[0].op)(2), 38);
+        assert_eq!((descs[2].op)(3), 9);
+        assert_eq!(descs[0].test, 23);
+        assert_eq!((descs[0].tbranch, descs[0].fbranch), (2, 3));
+    }
+
+    #[test]
+    fn invalid_input() {
+        let monkey = "Monkey 0:\n  Starting items: 1, 2\n  Operation: new = old * 19\n  \
+            Test: divisible by 23\n    If true: throw to monkey 0\n    If false: throw to monkey 1";
+
+        assert_eq!(
+            input(monkey).unwrap_err(),
+            ParseError::new(6, 31, "existing monkey")
+        );
+        assert_eq!(
+            input(&monkey.replace("* 19", "/ 19")).unwrap_err(),
+            ParseError::new(3, 24, "operator `+` or `*`")
+        );
+        assert_eq!(
+            input(&monkey.replace("1, 2", "1, x")).unwrap_err(),
+            ParseError::new(2, 22, "number")
+        );
+        assert_eq!(
+            input("Monkey 0:\n  Starting items: 1").unwrap_err(),
+            ParseError::new(2, 20, "`Operation:`")
+        );
+        assert_eq!(
+            input(&monkey.replace("monkey 1", "monkey 0")).unwrap_err(),
+            ParseError::new(6, 32, "monkey 1")
+        );
+        assert_eq!(input("\n").unwrap_err(), ParseError::new(2, 1, "monkey 0"));
+    }
+
+    #[test]
+    fn part1_test() {
+        let (descs, states) = input(EXAMPLE).unwrap();
+        assert_eq!(part1(&descs, states), 10605);
+    }
+
+    #[test]
+    fn part2_test() {
+        let (descs, states) = input(EXAMPLE).unwrap();
+        assert_eq!(part2(&descs, states), 2713310158);
+    }
+}
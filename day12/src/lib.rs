@@ -0,0 +1,140 @@
+use std::fmt::Display;
+
+use anyhow::Result;
+use aoc_common::search::bfs;
+use aoc_common::{Grid, ParseError, Solution, Source};
+
+type Pos = (usize, usize);
+
+#[derive(Debug)]
+pub struct Map {
+    map: Grid<u8>,
+    start: Pos,
+    end: Pos,
+}
+
+impl Map {
+    fn neighbors(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        let h = self.map[pos];
+
+        self.map
+            .neighbors4(pos)
+            .filter(move |p| self.map[*p] <= h + 1)
+    }
+}
+
+fn input(input: &str) -> Result<Map, ParseError> {
+    let source = Source::new(input);
+    let map = Grid::parse(input, "elevation `a`-`z`, `S` or `E`", |c| {
+        matches!(c, 'a'..='z' | 'S' | 'E').then_some(c as u8)
+    })?;
+
+    let start = map
+        .position(|&c| c == b'S')
+        .ok_or_else(|| source.error_after(input, "start `S` on the map"))?;
+    let end = map
+        .position(|&c| c == b'E')
+        .ok_or_else(|| source.error_after(input, "end `E` on the map"))?;
+
+    let map = map.map(|h| match *h {
+        b'S' => 0,
+        b'E' => b'z' - b'a',
+        h => h - b'a',
+    });
+
+    Ok(Map { map, start, end })
+}
+
+fn part1(map: &Map) -> usize {
+    bfs([map.start], |p: &Pos| map.neighbors(*p))
+        .get(&map.end)
+        .copied()
+        .unwrap_or(usize::MAX)
+}
+
+fn part2(map: &Map) -> usize {
+    let starts = map.map.iter().filter(|(_, h)| **h == 0).map(|(p, _)| p);
+
+    bfs(starts, |p: &Pos| map.neighbors(*p))
+        .get(&map.end)
+        .copied()
+        .unwrap_or(usize::MAX)
+}
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    const DAY: u8 = 12;
+
+    type Input = Map;
+
+    fn parse(data: &str) -> Result<Self::Input, ParseError> {
+        input(data)
+    }
+
+    fn part1(input: &Self::Input) -> Result<impl Display> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input) -> Result<impl Display> {
+        Ok(part2(input))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::search::astar;
+
+    const EXAMPLE: &str = include_str!("../test.in");
+
+    #[test]
+    fn input_test() {
+        let map = input(EXAMPLE).unwrap();
+
+        assert_eq!((map.map.width(), map.map.height()), (8, 5));
+        assert_eq!(map.start, (0, 0));
+        assert_eq!(map.end, (5, 2));
+        assert_eq!(map.map[map.start], 0);
+        assert_eq!(map.map[map.end], 25);
+    }
+
+    #[test]
+    fn invalid_input() {
+        assert_eq!(
+            input("Sab\nc1E").unwrap_err(),
+            ParseError::new(2, 2, "elevation `a`-`z`, `S` or `E`")
+        );
+        assert_eq!(
+            input("Sab\ncE").unwrap_err(),
+            ParseError::new(2, 3, "row of width 3")
+        );
+        assert_eq!(
+            input("Sab\ncde").unwrap_err(),
+            ParseError::new(2, 4, "end `E` on the map")
+        );
+    }
+
+    #[test]
+    fn part1_test() {
+        assert_eq!(part1(&input(EXAMPLE).unwrap()), 31);
+    }
+
+    #[test]
+    fn part2_test() {
+        assert_eq!(part2(&input(EXAMPLE).unwrap()), 29);
+    }
+
+    #[test]
+    fn astar_test() {
+        let map = input(EXAMPLE).unwrap();
+        let neighbors = |p: &Pos| map.neighbors(*p).map(|n| (n, 1));
+        let heuristic = |&(x, y): &Pos| (x.abs_diff(map.end.0) + y.abs_diff(map.end.1)) as u64;
+
+        let (steps, path) = astar([map.start], neighbors, heuristic, |p| *p == map.end).unwrap();
+
+        assert_eq!(steps, 31);
+        assert_eq!(path.len(), 32);
+        assert_eq!(path.first(), Some(&map.start));
+    }
+}
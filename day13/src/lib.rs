@@ -0,0 +1,191 @@
+use std::fmt::Display;
+
+use anyhow::Result;
+use aoc_common::{ParseError, Solution, Source};
+use nom::branch::alt;
+use nom::bytes::complete::tag;
+use nom::character::complete::digit1;
+use nom::combinator::map_res;
+use nom::multi::{separated_list0, separated_list1};
+use nom::sequence::{delimited, tuple};
+use nom::{IResult, Parser};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Packet {
+    Number(u128),
+    List(Vec<Packet>),
+}
+
+fn cmp_lists(l: &[Packet], r: &[Packet]) -> std::cmp::Ordering {
+    l.iter()
+        .zip(r)
+        .map(|(l, r)| l.cmp(r))
+        .find(|o| *o != std::cmp::Ordering::Equal)
+        .unwrap_or_else(|| l.len().cmp(&r.len()))
+}
+
+impl PartialOrd for Packet {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Packet {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        use Packet::*;
+        match (self, other) {
+            (Number(a), Number(b)) => a.cmp(b),
+            (List(a), List(b)) => cmp_lists(a, b),
+            (a @ Number(_), List(b)) => cmp_lists(std::slice::from_ref(a), b),
+            (List(a), b @ Number(_)) => cmp_lists(a, std::slice::from_ref(b)),
+        }
+    }
+}
+
+fn parse_num(input: &str) -> IResult<&str, Packet> {
+    map_res(digit1, |s: &str| s.parse::<u128>().map(Packet::Number))(input)
+}
+
+fn parse_list(input: &str) -> IResult<&str, Packet> {
+    let item = alt((parse_num, parse_list));
+    let items = separated_list0(tag(","), item).map(Packet::List);
+    delimited(tag("["), items, tag("]"))(input)
+}
+
+fn packet_list(input: &str) -> IResult<&str, Vec<[Packet; 2]>> {
+    let pair = tuple((parse_list, tag("\n"), parse_list)).map(|(a, _, b)| [a, b]);
+    separated_list1(tag("\n\n"), pair)(input)
+}
+
+fn input(input: &str) -> Result<Vec<[Packet; 2]>, ParseError> {
+    let source = Source::new(input);
+
+    let (rest, output) =
+        packet_list(input.trim()).map_err(|err| source.nom_error(err, "packet list"))?;
+
+    if !rest.is_empty() {
+        // The list stops before the first malformed pair, parsing it again to locate the error
+        let rest = rest.trim_start();
+        return Err(match tuple((parse_list, tag("\n"), parse_list))(rest) {
+            Err(err) => source.nom_error(err, "pair of packet lists"),
+            Ok(_) => source.error(rest, "blank line between pairs"),
+        });
+    }
+
+    Ok(output)
+}
+
+fn part1(input: &[[Packet; 2]]) -> usize {
+    input
+        .iter()
+        .enumerate()
+        .filter(|(_, [a, b])| a <= b)
+        .map(|(i, _)| i + 1)
+        .sum()
+}
+
+fn part2(input: &[[Packet; 2]]) -> usize {
+    let delim1 = Packet::List(vec![Packet::List(vec![Packet::Number(2)])]);
+    let delim2 = Packet::List(vec![Packet::List(vec![Packet::Number(6)])]);
+
+    let mut data: Vec<_> = input
+        .iter()
+        .flatten()
+        .cloned()
+        .chain([delim1.clone(), delim2.clone()])
+        .collect();
+
+    data.sort_unstable();
+
+    let delim1pos = data.binary_search(&delim1).unwrap() + 1;
+    let delim2pos = data.binary_search(&delim2).unwrap() + 1;
+
+    delim1pos * delim2pos
+}
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    const DAY: u8 = 13;
+
+    type Input = Vec<[Packet; 2]>;
+
+    fn parse(data: &str) -> Result<Self::Input, ParseError> {
+        input(data)
+    }
+
+    fn part1(input: &Self::Input) -> Result<impl Display> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input) -> Result<impl Display> {
+        Ok(part2(input))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../test.in");
+
+    #[test]
+    fn input_test() {
+        use Packet::*;
+
+        let pairs = input(EXAMPLE).unwrap();
+
+        assert_eq!(pairs.len(), 8);
+        assert_eq!(
+            pairs[1],
+            [
+                List(vec![
+                    List(vec![Number(1)]),
+                    List(vec![Number(2), Number(3), Number(4)])
+                ]),
+                List(vec![List(vec![Number(1)]), Number(4)]),
+            ]
+        );
+    }
+
+    #[test]
+    fn invalid_input() {
+        assert_eq!(
+            input("[1]\n[2]\n\n[1,x]\n[2]").unwrap_err(),
+            ParseError::new(4, 3, "pair of packet lists")
+        );
+        assert_eq!(
+            input("[1]\n[2]\n\n[1]\n\n[2]").unwrap_err(),
+            ParseError::new(5, 1, "pair of packet lists")
+        );
+        assert_eq!(
+            input("[1]\n[2]\n[3]\n[4]").unwrap_err(),
+            ParseError::new(3, 1, "blank line between pairs")
+        );
+        assert_eq!(
+            input("1]\n[2]").unwrap_err(),
+            ParseError::new(1, 1, "packet list")
+        );
+    }
+
+    #[test]
+    fn ordering_test() {
+        let pairs = input(EXAMPLE).unwrap();
+        let ordered: Vec<_> = pairs.iter().map(|[a, b]| a < b).collect();
+
+        assert_eq!(
+            ordered,
+            vec![true, true, false, true, false, true, false, false]
+        );
+    }
+
+    #[test]
+    fn part1_test() {
+        assert_eq!(part1(&input(EXAMPLE).unwrap()), 13);
+    }
+
+    #[test]
+    fn part2_test() {
+        assert_eq!(part2(&input(EXAMPLE).unwrap()), 140);
+    }
+}
@@ -0,0 +1,244 @@
+use std::fmt::Display;
+
+use anyhow::Result;
+use aoc_common::{Grid, ParseError, Solution, Source};
+
+fn input(input: &str) -> Result<Vec<Vec<(usize, usize)>>, ParseError> {
+    let source = Source::new(input);
+
+    let paths = aoc_common::input::non_empty_lines(input)
+        .map(|line| {
+            let mut path: Vec<(usize, usize)> = vec![];
+
+            for pos in line.split("->").map(str::trim) {
+                let (x, y) = source.split_once(pos, ",")?;
+                let (x, y) = (source.number(x.trim())?, source.number(y.trim())?);
+
+                // Segments of the path have to be horizontal or vertical
+                if let Some(&(px, py)) = path.last() {
+                    if px != x && py != y {
+                        return Err(source.error(pos, "point in line with the previous one"));
+                    }
+                }
+
+                path.push((x, y));
+            }
+
+            Ok(path)
+        })
+        .collect::<Result<Vec<_>, ParseError>>()?;
+
+    if paths.is_empty() {
+        return Err(source.error_after(input, "at least one rock path"));
+    }
+
+    Ok(paths)
+}
+
+fn rocks(input: &[Vec<(usize, usize)>], width: usize, height: usize) -> Grid<bool> {
+    let mut surface = Grid::new(width, height, false);
+
+    for line in input {
+        for segment in line.windows(2) {
+            let (x0, y0) = segment[0];
+            let (x1, y1) = segment[1];
+
+            if x0 == x1 {
+                for y in y0.min(y1)..=y0.max(y1) {
+                    surface[(x0, y)] = true;
+                }
+            } else {
+                for x in x0.min(x1)..=x0.max(x1) {
+                    surface[(x, y0)] = true;
+                }
+            }
+        }
+    }
+
+    surface
+}
+
+fn part1(input: &[Vec<(usize, usize)>]) -> usize {
+    let maxx = input
+        .iter()
+        .flat_map(|v| v.iter())
+        .max_by_key(|(x, _)| *x)
+        .unwrap()
+        .0
+        .max(500);
+
+    let maxy = input
+        .iter()
+        .flat_map(|v| v.iter())
+        .max_by_key(|(_, y)| *y)
+        .unwrap()
+        .1;
+
+    let mut surface = rocks(input, maxx + 1, maxy + 1);
+
+    std::iter::from_fn(|| {
+        let (x, y) = std::iter::successors(Some((500, 0)), |(x, y)| {
+            if *y > maxy {
+                None
+            } else if *y == maxy {
+                (*x, *y + 1).into()
+            } else if !surface[(*x, *y + 1)] {
+                Some((*x, *y + 1))
+            } else if *x == 0 {
+                Some((*x, maxy + 1))
+            } else if !surface[(*x - 1, *y + 1)] {
+                Some((*x - 1, *y))
+            } else if *x == maxx {
+                Some((*x, maxy + 1))
+            } else if !surface[(*x + 1, *y + 1)] {
+                Some((*x + 1, *y))
+            } else {
+                None
+            }
+        })
+        .last()
+        .unwrap();
+
+        if y > maxy {
+            None
+        } else {
+            surface[(x, y)] = true;
+
+            Some(())
+        }
+    })
+    .count()
+}
+
+fn part2(input: &[Vec<(usize, usize)>]) -> usize {
+    let maxy = input
+        .iter()
+        .flat_map(|v| v.iter())
+        .max_by_key(|(_, y)| *y)
+        .unwrap()
+        .1
+        + 1;
+
+    // The pile spreads up to `maxy` to the left of the source, so if it would reach past x = 0 all
+    // the rocks are shifted to the right to make room for it
+    let shift = (maxy + 1).saturating_sub(500);
+    let source = 500 + shift;
+    let input: Vec<Vec<_>> = input
+        .iter()
+        .map(|path| path.iter().map(|(x, y)| (x + shift, *y)).collect())
+        .collect();
+
+    let maxx = input
+        .iter()
+        .flat_map(|v| v.iter())
+        .max_by_key(|(x, _)| *x)
+        .unwrap()
+        .0
+        .max(source + maxy + 1);
+
+    let mut surface = rocks(&input, maxx + 1, maxy + 1);
+
+    std::iter::from_fn(|| {
+        let (x, y) = std::iter::successors(Some((source, 0)), |(x, y)| {
+            if *y == maxy {
+                None
+            } else if !surface[(*x, *y + 1)] {
+                Some((*x, *y + 1))
+            } else if !surface[(*x - 1, *y + 1)] {
+                Some((*x - 1, *y))
+            } else if !surface[(*x + 1, *y + 1)] {
+                Some((*x + 1, *y))
+            } else {
+                None
+            }
+        })
+        .last()
+        .unwrap();
+
+        if (x, y) == (source, 0) {
+            None
+        } else {
+            surface[(x, y)] = true;
+
+            Some(())
+        }
+    })
+    .count()
+        + 1
+}
+
+pub struct Day14;
+
+impl Solution for Day14 {
+    const DAY: u8 = 14;
+
+    type Input = Vec<Vec<(usize, usize)>>;
+
+    fn parse(data: &str) -> Result<Self::Input, ParseError> {
+        input(data)
+    }
+
+    fn part1(input: &Self::Input) -> Result<impl Display> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input) -> Result<impl Display> {
+        Ok(part2(input))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../test.in");
+
+    #[test]
+    fn input_test() {
+        assert_eq!(
+            input(EXAMPLE).unwrap(),
+            vec![
+                vec![(498, 4), (498, 6), (496, 6)],
+                vec![(503, 4), (502, 4), (502, 9), (494, 9)],
+            ]
+        );
+    }
+
+    #[test]
+    fn invalid_input() {
+        assert_eq!(
+            input("498,4 -> 498,6\n503,4 -> 502 4"),
+            Err(ParseError::new(2, 15, "`,`"))
+        );
+        assert_eq!(
+            input("498,4 -> 498,x"),
+            Err(ParseError::new(1, 14, "number"))
+        );
+        assert_eq!(
+            input("498,4 -> 497,6"),
+            Err(ParseError::new(
+                1,
+                10,
+                "point in line with the previous one"
+            ))
+        );
+        assert_eq!(
+            input("\n"),
+            Err(ParseError::new(2, 1, "at least one rock path"))
+        );
+    }
+
+    #[test]
+    fn part1_test() {
+        assert_eq!(part1(&input(EXAMPLE).unwrap()), 24);
+    }
+
+    #[test]
+    fn part2_test() {
+        assert_eq!(part2(&input(EXAMPLE).unwrap()), 93);
+
+        // The pile is wider than the space left of the source: 502 rows of sand, without the 3
+        // rocks at its left edge and the 3 cells sheltered below them
+        assert_eq!(part2(&input("0,500 -> 2,500").unwrap()), 502 * 502 - 6);
+    }
+}
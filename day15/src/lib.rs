@@ -0,0 +1,154 @@
+use std::collections::HashSet;
+use std::fmt::Display;
+
+use anyhow::{Context, Result};
+use aoc_common::{Interval, IntervalSet, ParseError, Solution, Source};
+
+type Pos = aoc_common::Pos<i128>;
+type Entry = (Pos, Pos);
+
+/// Row inspected in part 1 of the puzzle
+const ROW: i128 = 2000000;
+
+/// Maximal coordinate of the distress beacon in part 2 of the puzzle
+const BOUND: i128 = 4000000;
+
+fn parse_pos(source: &Source, pos: &str) -> Result<Pos, ParseError> {
+    let pos = source.strip_prefix(pos, "x=")?;
+    let (x, y) = source.split_once(pos, ", y=")?;
+
+    Ok(Pos::new(source.number(x)?, source.number(y)?))
+}
+
+fn input(input: &str) -> Result<Vec<Entry>, ParseError> {
+    let source = Source::new(input);
+
+    let entries = aoc_common::input::non_empty_lines(input)
+        .map(|line| {
+            let line = source.strip_prefix(line.trim(), "Sensor at ")?;
+            let (sensor, beacon) = source.split_once(line, ": closest beacon is at ")?;
+
+            Ok((parse_pos(&source, sensor)?, parse_pos(&source, beacon)?))
+        })
+        .collect::<Result<Vec<_>, ParseError>>()?;
+
+    if entries.is_empty() {
+        return Err(source.error_after(input, "at least one sensor"));
+    }
+
+    Ok(entries)
+}
+
+/// Number of positions in the row `y` where a beacon cannot be present
+fn part1(entries: &[Entry], y: i128) -> usize {
+    let covered: IntervalSet<i128> = entries
+        .iter()
+        .filter_map(|(sensor, beacon)| {
+            let a = sensor.manhattan(*beacon) - (sensor.y - y).abs();
+            (a >= 0).then(|| Interval::new(sensor.x - a, sensor.x + a))
+        })
+        .collect();
+
+    let beacons: HashSet<_> = entries
+        .iter()
+        .filter(|(_, beacon)| beacon.y == y && covered.contains_value(beacon.x))
+        .map(|(_, beacon)| beacon.x)
+        .collect();
+
+    covered.len() as usize - beacons.len()
+}
+
+/// Tuning frequency of the only possible beacon position with both coordinates in `0..=a`, `None`
+/// if every position is covered by the sensors
+fn part2(entries: &[Entry], a: i128) -> Option<i128> {
+    let mut sonars: Vec<_> = entries
+        .iter()
+        .map(|(sensor, beacon)| (*sensor, sensor.manhattan(*beacon)))
+        .collect();
+
+    sonars.sort_by_key(|&(_, d)| -(d as i64));
+    let Pos { x, y } = sonars
+        .iter()
+        .flat_map(|(Pos { x, y }, d)| {
+            let d = d + 1;
+
+            let xmin = (x - d).max(0);
+            let ymin = (y - d).max(0);
+
+            (xmin..=(x - d + y - ymin).max(a)).map(move |px| Pos::new(px, y - d + x - px))
+        })
+        .filter(|p| (0..=a).contains(&p.x) && (0..=a).contains(&p.y))
+        .find(|p| sonars.iter().all(|(s, d)| p.manhattan(*s) > *d))?;
+
+    const M: i128 = 4000000;
+    Some(M * x + y)
+}
+
+pub struct Day15;
+
+impl Solution for Day15 {
+    const DAY: u8 = 15;
+
+    type Input = Vec<Entry>;
+
+    fn parse(data: &str) -> Result<Self::Input, ParseError> {
+        input(data)
+    }
+
+    fn part1(input: &Self::Input) -> Result<impl Display> {
+        Ok(part1(input, ROW))
+    }
+
+    fn part2(input: &Self::Input) -> Result<impl Display> {
+        part2(input, BOUND).context("Every position is covered by the sensors")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../test.in");
+
+    #[test]
+    fn input_test() {
+        let entries = input(EXAMPLE).unwrap();
+
+        assert_eq!(entries.len(), 14);
+        assert_eq!(entries[0], (Pos::new(2, 18), Pos::new(-2, 15)));
+    }
+
+    #[test]
+    fn invalid_input() {
+        assert_eq!(
+            input("Sensor at x=2, y=18: closest beacon is at x=-2, y=a"),
+            Err(ParseError::new(1, 51, "number"))
+        );
+        assert_eq!(
+            input("Sensor at x=2, y=18; closest beacon is at x=-2, y=15"),
+            Err(ParseError::new(1, 53, "`: closest beacon is at `"))
+        );
+        assert_eq!(
+            input("Sensor at x=2 y=18: closest beacon is at x=-2, y=15"),
+            Err(ParseError::new(1, 19, "`, y=`"))
+        );
+        assert_eq!(input(""), Err(ParseError::new(1, 1, "at least one sensor")));
+        assert_eq!(
+            part2(
+                &input("Sensor at x=1, y=1: closest beacon is at x=1, y=3").unwrap(),
+                1
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn part1_test() {
+        assert_eq!(part1(&input(EXAMPLE).unwrap(), 10), 26);
+    }
+
+    #[test]
+    fn part2_test() {
+        assert_eq!(part2(&input(EXAMPLE).unwrap(), 20), Some(56000011));
+    }
+}
@@ -0,0 +1,379 @@
+use anyhow::Result;
+use aoc_common::search::floyd_warshall;
+use aoc_common::{Grid, ParseError, Solution, Source};
+use smallvec::{smallvec, SmallVec};
+use std::collections::HashMap;
+use std::fmt::Display;
+
+fn input(input: &str) -> Result<HashMap<&str, (i64, Vec<&str>)>, ParseError> {
+    let source = Source::new(input);
+
+    let valves = aoc_common::input::non_empty_lines(input)
+        .map(|line| {
+            let valve = source.strip_prefix(line.trim(), "Valve ")?;
+            let (name, flow) = source.split_once(valve, " has flow rate=")?;
+            let (flow, tunnels) = source.split_once(flow, "; ")?;
+            let flow = source.number(flow)?;
+            let tunnels = ["tunnels lead to valves ", "tunnel leads to valve "]
+                .into_iter()
+                .find_map(|prefix| tunnels.strip_prefix(prefix))
+                .ok_or_else(|| source.error(tunnels, "`tunnels lead to valves`"))?;
+            let tunnels = tunnels.split(',').map(str::trim).collect();
+
+            Ok((name, (flow, tunnels)))
+        })
+        .collect::<Result<HashMap<&str, (i64, Vec<&str>)>, ParseError>>()?;
+
+    if !valves.contains_key("AA") {
+        return Err(source.error_after(input, "valve `AA`"));
+    }
+
+    let unknown = valves
+        .values()
+        .flat_map(|(_, tunnels)| tunnels)
+        .filter(|tunnel| !valves.contains_key(*tunnel))
+        .min_by_key(|tunnel| tunnel.as_ptr());
+
+    match unknown {
+        Some(tunnel) => Err(source.error(tunnel, "existing valve")),
+        None => Ok(valves),
+    }
+}
+
+type Tunnels = SmallVec<[usize; 5]>;
+
+/// Indexes valves in alphabetical order, returns them along with the index of `AA`
+///
+/// All the tunnels lead to existing valves, as verified by `input`.
+fn preprocess(input: HashMap<&str, (i64, Vec<&str>)>) -> (Vec<(i64, Tunnels)>, usize) {
+    let mut key: Vec<_> = input.keys().collect();
+    key.sort();
+
+    let input = input.iter().fold(
+        vec![(0, smallvec![]); key.len()],
+        |mut valves, (name, (rate, tunnels))| {
+            let idx = key.binary_search(&name).unwrap();
+            let tunnels = tunnels
+                .iter()
+                .map(|tunnel| key.binary_search(&tunnel).unwrap())
+                .collect();
+
+            valves[idx] = (*rate, tunnels);
+            valves
+        },
+    );
+
+    let start = key.binary_search(&&"AA").unwrap();
+    (input, start)
+}
+
+/// Distances between all the valve pairs, `i64::MAX` for unreachable ones
+fn build_distance_table(input: &[(i64, Tunnels)]) -> Grid<i64> {
+    let tunnels = input
+        .iter()
+        .enumerate()
+        .flat_map(|(idx, (_, tunnels))| tunnels.iter().map(move |tunnel| (idx, *tunnel, 1)));
+
+    floyd_warshall(input.len(), tunnels).map(|dist| dist.map_or(i64::MAX, |dist| dist as i64))
+}
+
+// pub fn next_permutation(nums: &mut [(usize, i64)]) -> bool {
+//     use std::cmp::Ordering;
+//     // or use feature(array_windows) on nightly
+//     let last_ascending = match nums.windows(2).rposition(|w| w[0] < w[1]) {
+//         Some(i) => i,
+//         None => {
+//             nums.reverse();
+//             return false;
+//         }
+//     };
+//
+//     let swap_with = nums[last_ascending + 1..]
+//         .binary_search_by(|n| nums[last_ascending].cmp(n).then(Ordering::Less))
+//         .unwrap_err(); // cannot fail because the binary search will never succeed
+//     nums.swap(last_ascending, last_ascending + swap_with);
+//     nums[last_ascending + 1..].reverse();
+//     true
+// }
+
+fn part1(input: &[(i64, Tunnels)], dist_table: &Grid<i64>, start: usize) -> i64 {
+    const TIME: i64 = 30;
+
+    let mut valves: Vec<_> = input
+        .iter()
+        .enumerate()
+        .map(|(idx, (rate, _))| (idx, *rate))
+        .filter(|(_, rate)| *rate > 0)
+        .collect();
+
+    valves.sort_by_key(|(_, rate)| *rate);
+
+    let mut released = vec![false; valves.len()];
+
+    // (valve_idx, flow, time_left)
+    let mut stack: Vec<(usize, i64, i64)> = Vec::with_capacity(valves.len());
+    let mut best = 0;
+    let mut start_from = 0;
+
+    loop {
+        loop {
+            // look a valve to add to the stack, so we still have time to release it
+            let (prev, flow, time) = stack
+                .last()
+                .map(|(prev, flow, time)| (valves[*prev].0, *flow, *time))
+                .unwrap_or((start, 0, TIME));
+
+            let Some((next, flow, time)) = valves
+            .iter()
+            .enumerate()
+            .skip(start_from)
+            .find_map(|(valve, (idx, next_flow))| {
+               let dist = dist_table[(prev, *idx)];
+
+               if released[valve] || dist >= time {
+                   return None;
+               }
+
+               let time = time - dist - 1;
+               let max_flow = valves.iter().enumerate().filter_map(|(idx, (_, rate))| {
+                   match released[idx] {
+                       true => None,
+                       false => Some(*rate),
+                   }
+               }).sum::<i64>() * time + flow;
+
+               if max_flow < best {
+                   return None;
+               }
+
+               let flow = flow + time * next_flow;
+
+               Some((valve, flow, time))
+            }) else {
+                break;
+            };
+
+            released[next] = true;
+            stack.push((next, flow, time));
+            start_from = 0;
+        }
+
+        // Update on best solution
+        best = best.max(stack.last().map(|(_, flow, _)| *flow).unwrap_or(0));
+
+        // Backtrack once
+        let Some((valve, _, _)) = stack.pop() else {
+            break;
+        };
+
+        released[valve] = false;
+        start_from = valve + 1;
+    }
+
+    best
+}
+
+fn part2(input: &[(i64, Tunnels)], dist_table: &Grid<i64>, start: usize) -> i64 {
+    const TIME: i64 = 26;
+
+    let mut valves: Vec<_> = input
+        .iter()
+        .enumerate()
+        .filter(|(_, (rate, _))| *rate > 0)
+        .map(|(idx, _)| idx)
+        .collect();
+
+    valves.sort_by_key(|idx| input[*idx].0);
+
+    let mut released: u16 = 0;
+    // (my_valve, el_valve, flow, my_time, el_time, force, added_valve)
+    let mut stack: Vec<(usize, usize, i64, i64, i64, usize, usize)> =
+        Vec::with_capacity(valves.len());
+    stack.push((start, start, 0, TIME, TIME, 0, 0));
+
+    let mut best = 0;
+    let mut start_from = 0;
+    // 0 = none, 1 = me, 2 = elephant
+    let mut force = 0;
+
+    loop {
+        loop {
+            // look a valve to add to the stack, so we still have time to release it
+            let (my_prev, el_prev, flow, my_time, el_time, _, _) = stack.last().unwrap();
+
+            let Some((my_next, el_next, flow, my_time, el_time, next_force, added)) = valves
+                .iter()
+                .enumerate()
+                .skip(start_from)
+            .find_map(|(idx, valve)| {
+                let (next_flow, _) = input[*valve];
+               let dist_my = dist_table[(*my_prev, *valve)];
+               let dist_el = dist_table[(*el_prev, *valve)];
+
+               let next_my_time = my_time - dist_my - 1;
+               let next_el_time = el_time - dist_el - 1;
+               if (released & 2 << idx) > 0 || (next_my_time < 1 && next_el_time < 1) {
+                   return None;
+               }
+
+               let force = if idx == start_from {
+                   force
+               } else {
+                   0
+               };
+
+               let (my_next, el_next, my_time, el_time, t, force) = match force {
+                   0 if next_my_time >= next_el_time => (*valve, *el_prev, next_my_time, *el_time, next_my_time, 2),
+                   0 => (*my_prev, *valve, *my_time, next_el_time, next_el_time, 1),
+                   1 if next_my_time > 0 => (*valve, *el_prev, next_my_time, *el_time, next_my_time, 0),
+                   2 if next_el_time > 0 => (*my_prev, *valve, *my_time, next_el_time, next_el_time, 0),
+                   _ => return None,
+               };
+
+               let flow = flow + t * next_flow;
+               let mut left: Vec<_> = (0..valves.len()).filter(|idx| (released & 2 << idx) == 0).map(|idx| input[valves[idx]].0).collect();
+               left.sort();
+               let (_, _, max_flow) = left.into_iter().rev().fold((my_time - 2, el_time - 2, flow), |(my_time, el_time, flow), f| {
+                   if my_time < 0 && el_time < 0 {
+                       (my_time, el_time, flow)
+                   } else if my_time > el_time {
+                       (my_time - 2, el_time, flow + f * my_time)
+                   } else {
+                       (my_time, el_time - 2, flow + f * el_time)
+                   }
+               });
+               if max_flow < best {
+                   return None;
+               }
+
+
+               Some((my_next, el_next, flow, my_time, el_time, force, idx))
+            }) else {
+                break;
+            };
+
+            released |= 2 << added;
+            stack.push((my_next, el_next, flow, my_time, el_time, next_force, added));
+            start_from = 0;
+            force = 0;
+        }
+
+        // Update on best solution
+        let score = stack
+            .last()
+            .map(|(_, _, flow, _, _, _, _)| *flow)
+            .unwrap_or(0);
+
+        best = best.max(score);
+
+        // Don't backtract if there is only one (root) element
+        if stack.len() == 1 {
+            break;
+        }
+
+        // Backtrack once
+        let Some((_, _, _, _, _, next_force, added)) = stack.pop() else {
+            break;
+        };
+
+        released &= !(2 << added);
+        force = next_force;
+
+        match force {
+            0 => start_from = added + 1,
+            _ => start_from = added,
+        }
+    }
+
+    best
+}
+
+pub struct Cave {
+    valves: Vec<(i64, Tunnels)>,
+    dist_table: Grid<i64>,
+    start: usize,
+}
+
+pub struct Day16;
+
+impl Solution for Day16 {
+    const DAY: u8 = 16;
+
+    type Input = Cave;
+
+    fn parse(data: &str) -> Result<Self::Input, ParseError> {
+        let (valves, start) = preprocess(input(data)?);
+        let dist_table = build_distance_table(&valves);
+
+        Ok(Cave {
+            valves,
+            dist_table,
+            start,
+        })
+    }
+
+    fn part1(input: &Self::Input) -> Result<impl Display> {
+        Ok(part1(&input.valves, &input.dist_table, input.start))
+    }
+
+    fn part2(input: &Self::Input) -> Result<impl Display> {
+        Ok(part2(&input.valves, &input.dist_table, input.start))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../test.in");
+
+    #[test]
+    fn input_test() {
+        let valves = input(EXAMPLE).unwrap();
+
+        assert_eq!(valves.len(), 10);
+        assert_eq!(valves["AA"], (0, vec!["DD", "II", "BB"]));
+        assert_eq!(valves["HH"], (22, vec!["GG"]));
+    }
+
+    #[test]
+    fn invalid_input() {
+        let valves = "Valve AA has flow rate=0; tunnels lead to valves BB, CC\n\
+            Valve BB has flow rate=x; tunnel leads to valve AA";
+
+        assert_eq!(input(valves), Err(ParseError::new(2, 24, "number")));
+        assert_eq!(
+            input(&valves.replace("=x", "=3")),
+            Err(ParseError::new(1, 54, "existing valve"))
+        );
+        assert_eq!(
+            input(&valves.replace("=x", "=3").replace("AA", "DD")),
+            Err(ParseError::new(2, 51, "valve `AA`"))
+        );
+    }
+
+    #[test]
+    fn distance_table_test() {
+        let (valves, start) = preprocess(input(EXAMPLE).unwrap());
+        let dist_table = build_distance_table(&valves);
+
+        // valves are indexed in alphabetical order, `AA` is 0, `DD` is 3, `HH` is 7, `JJ` is 9
+        assert_eq!(start, 0);
+        assert_eq!(dist_table[(start, 3)], 1);
+        assert_eq!(dist_table[(start, 7)], 5);
+        assert_eq!(dist_table[(start, 9)], 2);
+        assert_eq!(dist_table[(7, 9)], 7);
+    }
+
+    #[test]
+    fn part1_test() {
+        let cave = Day16::parse(EXAMPLE).unwrap();
+        assert_eq!(part1(&cave.valves, &cave.dist_table, cave.start), 1651);
+    }
+
+    #[test]
+    fn part2_test() {
+        let cave = Day16::parse(EXAMPLE).unwrap();
+        assert_eq!(part2(&cave.valves, &cave.dist_table, cave.start), 1707);
+    }
+}
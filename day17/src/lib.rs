@@ -0,0 +1,294 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt::Display;
+
+use anyhow::Result;
+use aoc_common::{Grid, ParseError, Solution, Source};
+
+// Shapes are "up side down" - low indexes determines bottom of the shape.
+//
+// Every row is u8 bitmask (left-to-right reading from low bits)
+const SHAPES: [&[u8]; 5] = [
+    // ####
+    &[0b1111],
+    // .#.
+    // ###
+    // .#.
+    &[0b10, 0b111, 0b10],
+    // ###
+    // ..#
+    // ..#
+    &[0b111, 0b100, 0b100],
+    // #
+    // #
+    // #
+    // #
+    &[0b1; 4],
+    // ##
+    // ##
+    &[0b11; 2],
+];
+
+// Width of the chamber
+const WIDTH: usize = 7;
+
+// Positions of the shape cells, with its bottom left corner on (x, y)
+fn cells(shape: &[u8], x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
+    shape.iter().enumerate().flat_map(move |(j, row)| {
+        (0..u8::BITS as usize)
+            .filter(move |i| row & (1 << i) > 0)
+            .map(move |i| (x + i, y + j))
+    })
+}
+
+fn collision(shape: &[u8], surface: &Grid<bool>, x: usize, y: usize) -> bool {
+    // Anything past the right edge is a collision, anything above the tower is free space
+    cells(shape, x, y).any(|(i, j)| i >= WIDTH || surface.get((i, j)).is_some_and(|rock| *rock))
+}
+
+fn push(dir: char, shape: &[u8], surface: &Grid<bool>, x: usize, y: usize) -> usize {
+    let newx = match dir {
+        '>' => x + 1,
+        '<' if x > 0 => x - 1,
+        _ /* cannot move */ => return x,
+    };
+
+    // If there is any part of the shape out of the surface, or on the already fixed rock, we have
+    // a collision which means we cannot move. We return the old x.
+    match collision(shape, surface, newx, y) {
+        true => x,
+        false => newx,
+    }
+}
+
+// Returns new y coordinate, but also information if it changed, so the new brick should be spawned
+fn fall(shape: &[u8], surface: &Grid<bool>, x: usize, y: usize) -> (usize, bool) {
+    if y == 0 {
+        return (0, true);
+    }
+
+    match collision(shape, surface, x, y - 1) {
+        true => (y, true),
+        false => (y - 1, false),
+    }
+}
+
+// Shape is blocked - fix it on the surface, growing the surface if needed
+fn fix(shape: &[u8], surface: &mut Grid<bool>, x: usize, y: usize) {
+    if surface.height() < y + shape.len() {
+        surface.resize_rows(y + shape.len(), false);
+    }
+
+    for pos in cells(shape, x, y) {
+        surface[pos] = true;
+    }
+}
+
+// Empty cells reachable from above the tower moving left, right and down, as `(x, depth)` with
+// the depth counted from the tower top
+//
+// Falling rocks never move up, so they can only ever get to these cells, and every cell next to
+// them which is not among them is a rock (or the floor). Nothing else in the tower matters for the
+// rocks to come, so towers with the same top surface grow the same way from then on.
+fn top_surface(surface: &Grid<bool>) -> Vec<(usize, usize)> {
+    let top = surface.height();
+    let mut reached: HashSet<_> = (0..WIDTH).map(|x| (x, top)).collect();
+    let mut stack: Vec<_> = reached.iter().copied().collect();
+
+    while let Some((x, y)) = stack.pop() {
+        let next = [
+            x.checked_sub(1).map(|x| (x, y)),
+            (x + 1 < WIDTH).then_some((x + 1, y)),
+            y.checked_sub(1).map(|y| (x, y)),
+        ];
+
+        for pos in next.into_iter().flatten() {
+            if !surface.get(pos).is_some_and(|rock| *rock) && reached.insert(pos) {
+                stack.push(pos);
+            }
+        }
+    }
+
+    let mut cells: Vec<_> = reached.into_iter().map(|(x, y)| (x, top - y)).collect();
+    cells.sort_unstable();
+    cells
+}
+
+#[allow(unused)]
+fn draw(shape: &[u8], surface: &Grid<bool>, x: usize, y: usize) {
+    let shape: Vec<_> = cells(shape, x, y).collect();
+
+    // Rows are counted from the floor, so they are drawn in reverse
+    for j in (0..surface.height().max(y + 4)).rev() {
+        for i in 0..WIDTH {
+            if surface.get((i, j)).is_some_and(|rock| *rock) {
+                print!("#");
+            } else if shape.contains(&(i, j)) {
+                print!("@");
+            } else {
+                print!(".");
+            }
+        }
+
+        println!();
+    }
+}
+
+#[derive(PartialEq, Eq, Hash, Clone)]
+struct State {
+    top_surface: Vec<(usize, usize)>,
+    next_shape: usize,
+    in_pos: usize,
+}
+
+fn tower_height(input: &str, rocks: usize) -> usize {
+    simulate(input, rocks, true)
+}
+
+// Height of the tower after `rocks` rocks fall, skipping the repeating cycles if `skip_cycles` is
+// set
+fn simulate(input: &str, rocks: usize, skip_cycles: bool) -> usize {
+    // The playing surface. Always 7-wide (by definition), rows are counted from the floor up and
+    // added while the tower is growing. `false` means empty space, `true` is fixed rock.
+    let mut surface = Grid::new(WIDTH, 0, false);
+    let mut next_shape = 1;
+    let mut shape = SHAPES[0];
+    let mut x = 2;
+    let mut y = 3;
+    let mut fixed_cnt = 0;
+    let mut skipped_lines = 0;
+
+    // Hash map of board states after fixing the shape. State contains the top surface of the
+    // tower, the next shape to be spawned and the jet position. The value of the map is the tower
+    // height when reaching the state, and the number of fixed rocks.
+    //
+    // If the state is reached again, it means we have the loop - we can calculate the cycle length
+    // (current_rocks_fixed - state_rocks_fixed) and it is clear, that every `cycle_length` steps
+    // the state would be repeated with the tower higher by `current_height - state_height`.
+    let mut states: HashMap<State, (usize, usize)> = HashMap::new();
+
+    for (idx, dir) in input.chars().enumerate().cycle() {
+        if fixed_cnt >= rocks {
+            break;
+        }
+
+        // Note: (x, y) is bottom left current shape coordinate, and they are going bot-to-top,
+        // and left-to-right.
+        x = push(dir, shape, &surface, x, y);
+        let (newy, spawn) = fall(shape, &surface, x, y);
+        y = newy;
+
+        if spawn {
+            fix(shape, &mut surface, x, y);
+
+            shape = SHAPES[next_shape];
+            next_shape = (next_shape + 1) % SHAPES.len();
+            x = 2;
+            y = surface.height() + 3;
+
+            fixed_cnt += 1;
+
+            if !skip_cycles {
+                continue;
+            }
+
+            let state = State {
+                top_surface: top_surface(&surface),
+                next_shape,
+                in_pos: idx,
+            };
+
+            // If instert returns `Some`, we met the state again - we can calculate the cycle
+            if let Some((cycle_height, cycle_fixed)) =
+                states.insert(state, (surface.height(), fixed_cnt))
+            {
+                let cycle_length = fixed_cnt - cycle_fixed;
+                let cycle_lines = surface.height() - cycle_height;
+                let cycles = (rocks - fixed_cnt) / cycle_length;
+
+                // Just jump forward as much as we can, only counting the skipped lines
+                fixed_cnt += cycles * cycle_length;
+                skipped_lines += cycles * cycle_lines;
+            }
+        }
+    }
+
+    surface.height() + skipped_lines
+}
+
+fn input(input: &str) -> Result<String, ParseError> {
+    let source = Source::new(input);
+    let jets = input.lines().next().unwrap_or_default().trim_end();
+
+    if let Some(idx) = jets.find(|c| c != '<' && c != '>') {
+        return Err(source.error(&jets[idx..], "jet `<` or `>`"));
+    }
+
+    if jets.is_empty() {
+        return Err(source.error(jets, "jet pattern"));
+    }
+
+    Ok(jets.to_owned())
+}
+
+pub struct Day17;
+
+impl Solution for Day17 {
+    const DAY: u8 = 17;
+
+    type Input = String;
+
+    fn parse(data: &str) -> Result<Self::Input, ParseError> {
+        input(data)
+    }
+
+    fn part1(input: &Self::Input) -> Result<impl Display> {
+        Ok(tower_height(input, 2022))
+    }
+
+    fn part2(input: &Self::Input) -> Result<impl Display> {
+        Ok(tower_height(input, 1000000000000))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../test.in");
+
+    #[test]
+    fn input_test() {
+        assert_eq!(input(EXAMPLE).unwrap().len(), 40);
+        assert_eq!(input("<>\n>>").unwrap(), "<>");
+    }
+
+    #[test]
+    fn invalid_input() {
+        assert_eq!(input("<><x"), Err(ParseError::new(1, 4, "jet `<` or `>`")));
+        assert_eq!(input("\n<>"), Err(ParseError::new(1, 1, "jet pattern")));
+    }
+
+    #[test]
+    fn part1_test() {
+        assert_eq!(tower_height(&input(EXAMPLE).unwrap(), 2022), 3068);
+    }
+
+    #[test]
+    fn cycles() {
+        let jets = input(EXAMPLE).unwrap();
+
+        assert_eq!(tower_height(&jets, 0), 0);
+        assert_eq!(tower_height(&jets, 1), 1);
+        for rocks in [10, 2022, 5000, 7777] {
+            assert_eq!(tower_height(&jets, rocks), simulate(&jets, rocks, false));
+        }
+    }
+
+    #[test]
+    fn part2_test() {
+        assert_eq!(
+            tower_height(&input(EXAMPLE).unwrap(), 1000000000000),
+            1514285714288
+        );
+    }
+}
@@ -0,0 +1,138 @@
+use std::collections::HashSet;
+use std::fmt::Display;
+
+use anyhow::Result;
+use aoc_common::search::bfs;
+use aoc_common::{ParseError, Solution, Source};
+
+type Cube = (i64, i64, i64);
+
+fn input(input: &str) -> Result<HashSet<Cube>, ParseError> {
+    let source = Source::new(input);
+
+    let cubes = aoc_common::input::non_empty_lines(input)
+        .map(|l| {
+            let (x, yz) = source.split_once(l.trim(), ",")?;
+            let (y, z) = source.split_once(yz, ",")?;
+
+            Ok((source.number(x)?, source.number(y)?, source.number(z)?))
+        })
+        .collect::<Result<HashSet<_>, ParseError>>()?;
+
+    if cubes.is_empty() {
+        return Err(source.error_after(input, "at least one cube"));
+    }
+
+    Ok(cubes)
+}
+
+fn neighbors((x, y, z): Cube) -> [Cube; 6] {
+    [
+        (x - 1, y, z),
+        (x + 1, y, z),
+        (x, y - 1, z),
+        (x, y + 1, z),
+        (x, y, z - 1),
+        (x, y, z + 1),
+    ]
+}
+
+fn part1(data: &HashSet<Cube>) -> usize {
+    let hidden: usize = data
+        .iter()
+        .map(|cube| {
+            neighbors(*cube)
+                .into_iter()
+                .filter(|n| data.contains(n))
+                .count()
+        })
+        .sum();
+
+    data.len() * 6 - hidden
+}
+
+fn part2(data: &HashSet<Cube>) -> usize {
+    let minx = *data.iter().map(|(x, _, _)| x).min().unwrap() - 1;
+    let miny = *data.iter().map(|(_, y, _)| y).min().unwrap() - 1;
+    let minz = *data.iter().map(|(_, _, z)| z).min().unwrap() - 1;
+    let maxx = *data.iter().map(|(x, _, _)| x).max().unwrap() + 1;
+    let maxy = *data.iter().map(|(_, y, _)| y).max().unwrap() + 1;
+    let maxz = *data.iter().map(|(_, _, z)| z).max().unwrap() + 1;
+
+    let in_bounds = |(x, y, z): &Cube| {
+        (minx..=maxx).contains(x) && (miny..=maxy).contains(y) && (minz..=maxz).contains(z)
+    };
+
+    // Flood fill the air around the droplet, every lava cube touched from outside is a surface
+    bfs([(minx, miny, minz)], |cube: &Cube| {
+        neighbors(*cube)
+            .into_iter()
+            .filter(|n| in_bounds(n) && !data.contains(n))
+    })
+    .into_keys()
+    .map(|cube| {
+        neighbors(cube)
+            .into_iter()
+            .filter(|n| data.contains(n))
+            .count()
+    })
+    .sum()
+}
+
+pub struct Day18;
+
+impl Solution for Day18 {
+    const DAY: u8 = 18;
+
+    type Input = HashSet<Cube>;
+
+    fn parse(data: &str) -> Result<Self::Input, ParseError> {
+        input(data)
+    }
+
+    fn part1(input: &Self::Input) -> Result<impl Display> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input) -> Result<impl Display> {
+        Ok(part2(input))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../test.in");
+
+    #[test]
+    fn input_test() {
+        let cubes = input(EXAMPLE).unwrap();
+
+        assert_eq!(cubes.len(), 13);
+        assert!(cubes.contains(&(2, 2, 2)));
+        assert!(cubes.contains(&(3, 2, 5)));
+    }
+
+    #[test]
+    fn invalid_input() {
+        assert_eq!(input("1,1,1\n2,1"), Err(ParseError::new(2, 4, "`,`")));
+        assert_eq!(input("1,1,1,1"), Err(ParseError::new(1, 5, "number")));
+        assert_eq!(input(""), Err(ParseError::new(1, 1, "at least one cube")));
+    }
+
+    #[test]
+    fn part1_small_test() {
+        assert_eq!(part1(&input("1,1,1\n2,1,1").unwrap()), 10);
+    }
+
+    #[test]
+    fn part1_test() {
+        assert_eq!(part1(&input(EXAMPLE).unwrap()), 64);
+    }
+
+    #[test]
+    fn part2_test() {
+        assert_eq!(part2(&input(EXAMPLE).unwrap()), 58);
+    }
+}
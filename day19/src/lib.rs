@@ -0,0 +1,210 @@
+use std::collections::HashSet;
+use std::fmt::Display;
+
+use anyhow::Result;
+use aoc_common::{ParseError, Solution, Source};
+
+type Blueprint = [[i32; 4]; 4];
+
+/// Robot kinds in the order of blueprint rows, which is also the order of resources they collect
+const ROBOTS: [&str; 4] = ["ore", "clay", "obsidian", "geode"];
+
+fn parse_cost(source: &Source, robot: &str, sentence: &str) -> Result<[i32; 4], ParseError> {
+    let costs = source.strip_prefix(sentence.trim(), &format!("Each {robot} robot costs "))?;
+    let mut cost = [0; 4];
+
+    for item in costs.split(" and ") {
+        let (amount, resource) = source.split_once(item, " ")?;
+        let idx = ROBOTS[..3]
+            .iter()
+            .position(|r| *r == resource)
+            .ok_or_else(|| source.error(resource, "resource `ore`, `clay` or `obsidian`"))?;
+
+        cost[idx] = source.number(amount)?;
+    }
+
+    Ok(cost)
+}
+
+fn input(input: &str) -> Result<Vec<Blueprint>, ParseError> {
+    let source = Source::new(input);
+
+    aoc_common::input::non_empty_lines(input)
+        .enumerate()
+        .map(|(idx, line)| {
+            let (id, robots) = source.split_once(line, ":")?;
+            let id = source.strip_prefix(id, "Blueprint ")?;
+            if source.number::<usize>(id)? != idx + 1 {
+                return Err(source.error(id, format!("blueprint {}", idx + 1)));
+            }
+
+            let mut sentences = robots.split('.');
+            let mut blueprint = [[0; 4]; 4];
+
+            for (cost, robot) in blueprint.iter_mut().zip(ROBOTS) {
+                let sentence = sentences.next().unwrap_or_default();
+                *cost = parse_cost(&source, robot, sentence)?;
+            }
+
+            Ok(blueprint)
+        })
+        .collect()
+}
+
+/// Maximal number of geodes which can be opened using the blueprint in the given time
+fn max_geodes(bp: &Blueprint, time: i32) -> i32 {
+    let mut cache = HashSet::new();
+    let mut best = 0;
+    let mut states = vec![((0, 0, 0, 0), [1, 0, 0, 0], 0)];
+
+    let m0 = *bp.iter().map(|[b0, _, _, _]| b0).max().unwrap();
+    let m1 = *bp.iter().map(|[_, b1, _, _]| b1).max().unwrap();
+    let m2 = *bp.iter().map(|[_, _, b2, _]| b2).max().unwrap();
+    let m = [m0, m1, m2];
+    while let Some(((r0, r1, r2, r3), [i0, i1, i2, i3], t)) = states.pop() {
+        if t == time {
+            best = best.max(r3);
+            continue;
+        }
+
+        if (r3 + (time + 1 - t) * i3 + (time - t) * (time + 1 - t) / 2) <= best {
+            // theoretical best is too bad, ignore
+            continue;
+        }
+
+        let i0 = i0.min(m0);
+        let i1 = i1.min(m1);
+        let i2 = i2.min(m2);
+
+        let r0 = r0.min(t * m0 - (t - 1) * i0);
+        let r1 = r1.min(t * m1 - (t - 1) * i1);
+        let r2 = r2.min(t * m2 - (t - 1) * i2);
+
+        if !cache.insert(((r0, r1, r2, r3), [i0, i1, i2, i3], t)) {
+            continue;
+        }
+
+        for (i, [c0, c1, c2, c3]) in bp.iter().copied().enumerate().rev() {
+            if c0 <= r0
+                && c1 <= r1
+                && c2 <= r2
+                && c3 <= r3
+                && (i == 3 || m[i] > [i0, i1, i2, i3][i])
+            {
+                let mut bots = [i0, i1, i2, i3];
+                bots[i] += 1;
+                let state = (
+                    (r0 - c0 + i0, r1 - c1 + i1, r2 - c2 + i2, r3 - c3 + i3),
+                    bots,
+                    t + 1,
+                );
+
+                states.push(state);
+            }
+        }
+
+        let state = (
+            (r0 + i0, r1 + i1, r2 + i2, r3 + i3),
+            [i0, i1, i2, i3],
+            t + 1,
+        );
+        states.push(state);
+    }
+
+    best
+}
+
+fn part1(blueprints: &[Blueprint]) -> i32 {
+    blueprints
+        .iter()
+        .enumerate()
+        .map(|(i, bp)| (i as i32 + 1) * max_geodes(bp, 24))
+        .sum()
+}
+
+fn part2(blueprints: &[Blueprint]) -> i32 {
+    blueprints
+        .iter()
+        .take(3)
+        .map(|bp| max_geodes(bp, 32))
+        .product()
+}
+
+pub struct Day19;
+
+impl Solution for Day19 {
+    const DAY: u8 = 19;
+
+    type Input = Vec<Blueprint>;
+
+    fn parse(data: &str) -> Result<Self::Input, ParseError> {
+        input(data)
+    }
+
+    fn part1(input: &Self::Input) -> Result<impl Display> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input) -> Result<impl Display> {
+        Ok(part2(input))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../test.in");
+
+    #[test]
+    fn input_test() {
+        assert_eq!(
+            input(EXAMPLE).unwrap(),
+            vec![
+                [[4, 0, 0, 0], [2, 0, 0, 0], [3, 14, 0, 0], [2, 0, 7, 0]],
+                [[2, 0, 0, 0], [3, 0, 0, 0], [3, 8, 0, 0], [3, 0, 12, 0]],
+            ]
+        );
+    }
+
+    #[test]
+    fn invalid_input() {
+        let line = EXAMPLE.lines().next().unwrap();
+
+        assert_eq!(
+            input(&line.replace("2 ore. Each obsidian", "2 gold. Each obsidian")),
+            Err(ParseError::new(
+                1,
+                66,
+                "resource `ore`, `clay` or `obsidian`"
+            ))
+        );
+        assert_eq!(
+            input(&line.replace(" Each geode robot costs 2 ore and 7 obsidian.", "")),
+            Err(ParseError::new(1, 115, "`Each geode robot costs `"))
+        );
+        assert_eq!(
+            input(&line.replace("Blueprint 1", "Blueprint 2")),
+            Err(ParseError::new(1, 11, "blueprint 1"))
+        );
+    }
+
+    #[test]
+    fn max_geodes_test() {
+        let blueprints = input(EXAMPLE).unwrap();
+
+        assert_eq!(max_geodes(&blueprints[0], 24), 9);
+        assert_eq!(max_geodes(&blueprints[1], 24), 12);
+    }
+
+    #[test]
+    fn part1_test() {
+        assert_eq!(part1(&input(EXAMPLE).unwrap()), 33);
+    }
+
+    #[test]
+    #[ignore = "takes over a minute in debug builds, run with `cargo test --release -- --ignored`"]
+    fn part2_test() {
+        assert_eq!(part2(&input(EXAMPLE).unwrap()), 3472);
+    }
+}
@@ -0,0 +1,676 @@
+use std::fmt::Display;
+use std::path::Path;
+
+use anyhow::{bail, ensure, Context, Result};
+use aoc_common::{ParseError, Solution, Source};
+use serde::Deserialize;
+
+/// Shape of the game with its score and the letters standing for it in the strategy guide
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct ShapeRule {
+    pub name: String,
+    pub points: u64,
+    /// Letter of the enemy play
+    pub enemy: char,
+    /// Letter of my play, when the guide is read as shapes to play
+    pub me: char,
+}
+
+/// Rules of a rock-paper-scissors variant with an odd number of shapes
+///
+/// Dominance is cyclic: shapes are listed so that every shape beats the half of the other shapes
+/// preceding it, wrapping around the start of the list. For the classic game it is rock, paper,
+/// scissors - paper beats rock, scissors beat paper and rock beats scissors.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct Rules {
+    pub shapes: Vec<ShapeRule>,
+    /// Letters of my play when the guide is read as outcomes: lose, draw, win
+    pub outcomes: [char; 3],
+    /// Points for the outcome: lose, draw, win
+    pub outcome_points: [u64; 3],
+}
+
+impl Rules {
+    /// Rock, paper, scissors as played by the elves
+    pub fn classic() -> Self {
+        let shape = |name: &str, points, enemy, me| ShapeRule {
+            name: name.to_owned(),
+            points,
+            enemy,
+            me,
+        };
+
+        Self {
+            shapes: vec![
+                shape("Rock", 1, 'A', 'X'),
+                shape("Paper", 2, 'B', 'Y'),
+                shape("Scissors", 3, 'C', 'Z'),
+            ],
+            outcomes: ['X', 'Y', 'Z'],
+            outcome_points: [0, 3, 6],
+        }
+    }
+
+    /// Parses rules from TOML, verifying they describe a playable game
+    pub fn from_toml(content: &str) -> Result<Self> {
+        let rules: Self = toml::from_str(content)?;
+
+        ensure!(
+            rules.shapes.len() >= 3,
+            "At least 3 shapes are needed for every shape to beat another one"
+        );
+        ensure!(
+            rules.shapes.len() % 2 == 1,
+            "Odd number of shapes is needed for every shape to beat the same number of others"
+        );
+
+        let unique = |letters: Vec<char>| {
+            let mut sorted = letters.clone();
+            sorted.sort_unstable();
+            sorted.dedup();
+            sorted.len() == letters.len()
+        };
+
+        ensure!(
+            unique(rules.shapes.iter().map(|shape| shape.enemy).collect()),
+            "Enemy letters of the shapes are not unique"
+        );
+        ensure!(
+            unique(rules.shapes.iter().map(|shape| shape.me).collect()),
+            "My letters of the shapes are not unique"
+        );
+        ensure!(
+            unique(rules.outcomes.to_vec()),
+            "Outcome letters are not unique"
+        );
+
+        Ok(rules)
+    }
+
+    /// Loads rules from a TOML file
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Cannot read rules file {}", path.display()))?;
+        Self::from_toml(&content).with_context(|| format!("Invalid rules file {}", path.display()))
+    }
+
+    fn outcome(&self, me: Shape, enemy: Shape) -> Outcome {
+        let n = self.shapes.len();
+
+        match (me.0 + n - enemy.0) % n {
+            0 => Outcome::Draw,
+            d if d <= n / 2 => Outcome::Win,
+            _ => Outcome::Lose,
+        }
+    }
+
+    /// Shape giving the expected outcome, if there are more winning or losing ones it is the
+    /// closest one in the dominance order
+    fn deduce(&self, enemy: Shape, expected: Outcome) -> Shape {
+        let n = self.shapes.len();
+
+        match expected {
+            Outcome::Draw => enemy,
+            Outcome::Win => Shape((enemy.0 + 1) % n),
+            Outcome::Lose => Shape((enemy.0 + n - 1) % n),
+        }
+    }
+
+    fn points(&self, shape: Shape, outcome: Outcome) -> u64 {
+        self.shapes[shape.0].points + self.outcome_points[outcome as usize]
+    }
+
+    /// Score of the round against `enemy` with my letter meaning `meaning`
+    fn score(&self, enemy: Shape, meaning: Meaning) -> u64 {
+        match meaning {
+            Meaning::Shape(me) => self.points(Shape(me), self.outcome(Shape(me), enemy)),
+            Meaning::Outcome(outcome) => self.points(self.deduce(enemy, outcome), outcome),
+        }
+    }
+
+    /// My letters read as shapes to play, as in the first part
+    pub fn shape_reading(&self) -> Interpretation {
+        let letters = self.shapes.iter().map(|shape| shape.me);
+        letters.zip((0..).map(Meaning::Shape)).collect()
+    }
+
+    /// My letters read as expected outcomes, as in the second part
+    pub fn outcome_reading(&self) -> Interpretation {
+        let outcomes = Outcome::ALL.into_iter().map(Meaning::Outcome);
+        self.outcomes.into_iter().zip(outcomes).collect()
+    }
+
+    /// Describes the interpretation, like "X = Rock, Y = Paper, Z = Scissors"
+    pub fn describe(&self, interpretation: &Interpretation) -> String {
+        let meanings: Vec<_> = interpretation
+            .iter()
+            .map(|(letter, meaning)| match meaning {
+                Meaning::Shape(shape) => format!("{letter} = {}", self.shapes[*shape].name),
+                Meaning::Outcome(outcome) => format!("{letter} = {outcome}"),
+            })
+            .collect();
+
+        meanings.join(", ")
+    }
+}
+
+/// Index of the shape in the rules
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Shape(usize);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Round {
+    enemy: Shape,
+    me: char,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Outcome {
+    Lose,
+    Draw,
+    Win,
+}
+
+impl Outcome {
+    const ALL: [Outcome; 3] = [Outcome::Lose, Outcome::Draw, Outcome::Win];
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Outcome::Lose => write!(f, "lose"),
+            Outcome::Draw => write!(f, "draw"),
+            Outcome::Win => write!(f, "win"),
+        }
+    }
+}
+
+/// What my letter in the guide stands for
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Meaning {
+    /// Index of the shape to play
+    Shape(usize),
+    /// Outcome to achieve
+    Outcome(Outcome),
+}
+
+/// Meanings of my letters in the guide
+pub type Interpretation = Vec<(char, Meaning)>;
+
+/// Describes the letters as alternatives, like "`A`, `B` or `C`"
+fn alternatives(letters: &[char]) -> String {
+    let letters: Vec<_> = letters.iter().map(|c| format!("`{c}`")).collect();
+
+    match letters.split_last() {
+        Some((last, [])) => last.clone(),
+        Some((last, rest)) => format!("{} or {last}", rest.join(", ")),
+        None => String::new(),
+    }
+}
+
+fn letter(s: &str) -> Option<char> {
+    let mut chars = s.chars();
+    chars.next().filter(|_| chars.next().is_none())
+}
+
+/// Parses the strategy guide with letters given by the rules
+pub fn parse_with(input: &str, rules: &Rules) -> Result<Vec<Round>, ParseError> {
+    let source = Source::new(input);
+
+    let enemy_letters: Vec<_> = rules.shapes.iter().map(|shape| shape.enemy).collect();
+    let mut my_letters: Vec<_> = rules.shapes.iter().map(|shape| shape.me).collect();
+    for letter in rules.outcomes {
+        if !my_letters.contains(&letter) {
+            my_letters.push(letter);
+        }
+    }
+
+    input
+        .lines()
+        .map(|line| {
+            let (player1, player2) = source.split_once(line, " ")?;
+
+            let enemy = letter(player1)
+                .and_then(|c| enemy_letters.iter().position(|l| *l == c))
+                .ok_or_else(|| {
+                    let expected = format!("enemy play {}", alternatives(&enemy_letters));
+                    source.error(player1, expected)
+                })?;
+
+            let me = letter(player2)
+                .filter(|c| my_letters.contains(c))
+                .ok_or_else(|| {
+                    let expected = format!("my play {}", alternatives(&my_letters));
+                    source.error(player2, expected)
+                })?;
+
+            Ok(Round {
+                enemy: Shape(enemy),
+                me,
+            })
+        })
+        .collect()
+}
+
+fn input(input: &str) -> Result<Vec<Round>, ParseError> {
+    parse_with(input, &Rules::classic())
+}
+
+/// Total score of the guide, `None` if any of my letters has no meaning in the interpretation
+pub fn total(rules: &Rules, input: &[Round], interpretation: &Interpretation) -> Option<u64> {
+    input
+        .iter()
+        .map(|round| {
+            let (_, meaning) = interpretation.iter().find(|(l, _)| *l == round.me)?;
+            Some(rules.score(round.enemy, *meaning))
+        })
+        .sum()
+}
+
+/// Total score with my letters read as shapes to play, `None` if any of them is not a shape
+pub fn part1(rules: &Rules, input: &[Round]) -> Option<u64> {
+    total(rules, input, &rules.shape_reading())
+}
+
+/// Total score with my letters read as outcomes, `None` if any of them is not an outcome
+pub fn part2(rules: &Rules, input: &[Round]) -> Option<u64> {
+    total(rules, input, &rules.outcome_reading())
+}
+
+/// All the permutations of `0..n`
+fn permutations(n: usize) -> Vec<Vec<usize>> {
+    if n == 0 {
+        return vec![vec![]];
+    }
+
+    permutations(n - 1)
+        .into_iter()
+        .flat_map(|perm| {
+            (0..n).map(move |at| {
+                let mut perm = perm.clone();
+                perm.insert(at, n - 1);
+                perm
+            })
+        })
+        .collect()
+}
+
+/// Every bijection of my letters to the shapes, and of the outcome letters to the outcomes
+pub fn interpretations(rules: &Rules) -> Vec<Interpretation> {
+    let shapes = rules.shape_reading();
+    let outcomes = rules.outcome_reading();
+
+    let permuted = |reading: Interpretation| {
+        permutations(reading.len()).into_iter().map(move |perm| {
+            perm.iter()
+                .zip(&reading)
+                .map(|(from, (letter, _))| (*letter, reading[*from].1))
+                .collect()
+        })
+    };
+
+    permuted(shapes).chain(permuted(outcomes)).collect()
+}
+
+/// Interpretations giving the highest and the lowest total score, along with the scores
+///
+/// Only the interpretations giving a meaning to all my letters in the guide are considered.
+pub fn optimize(
+    rules: &Rules,
+    input: &[Round],
+) -> Option<((u64, Interpretation), (u64, Interpretation))> {
+    let totals: Vec<_> = interpretations(rules)
+        .into_iter()
+        .filter_map(|interpretation| Some((total(rules, input, &interpretation)?, interpretation)))
+        .collect();
+
+    let best = totals.iter().max_by_key(|(total, _)| *total)?;
+    let worst = totals.iter().min_by_key(|(total, _)| *total)?;
+
+    Some((best.clone(), worst.clone()))
+}
+
+/// Opponent playing instead of the enemy column of the guide
+#[derive(Clone, Copy, Debug, PartialEq, Eq, clap::ValueEnum)]
+pub enum Opponent {
+    /// Plays every shape with the same probability
+    Uniform,
+    /// Plays shapes as often as the enemy plays them in the guide
+    Frequency,
+}
+
+impl Opponent {
+    /// Probability of playing every shape of the rules
+    fn weights(self, rules: &Rules, input: &[Round]) -> Vec<f64> {
+        let n = rules.shapes.len();
+
+        match self {
+            Opponent::Uniform => vec![1. / n as f64; n],
+            Opponent::Frequency => {
+                let mut counts = vec![0.; n];
+                for round in input {
+                    counts[round.enemy.0] += 1.;
+                }
+
+                counts.iter().map(|c| c / input.len() as f64).collect()
+            }
+        }
+    }
+}
+
+/// SplitMix64 generator, so the simulation is reproducible with the same seed
+struct Rng(u64);
+
+impl Rng {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e3779b97f4a7c15);
+        let z = self.0;
+        let z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        let z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform number in `[0, 1)`
+    fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+/// Distribution of total scores of the guide played against the opponent
+#[derive(Debug, Clone, PartialEq)]
+pub struct Distribution {
+    /// Exact expected total
+    pub expected: f64,
+    /// Mean of the simulated totals
+    pub mean: f64,
+    pub std_dev: f64,
+    /// Simulated totals at 0th, 10th, 50th, 90th and 100th percentile
+    pub percentiles: [u64; 5],
+}
+
+impl Display for Distribution {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let [min, p10, median, p90, max] = self.percentiles;
+
+        writeln!(f, "Expected: {:.1}", self.expected)?;
+        writeln!(f, "Simulated: {:.1} ± {:.1}", self.mean, self.std_dev)?;
+        writeln!(
+            f,
+            "Min: {min}, 10%: {p10}, median: {median}, 90%: {p90}, max: {max}"
+        )
+    }
+}
+
+/// Plays the guide read with the interpretation against the opponent `trials` times
+///
+/// Fails if any of my letters has no meaning in the interpretation, or there are no trials.
+pub fn simulate(
+    rules: &Rules,
+    input: &[Round],
+    interpretation: &Interpretation,
+    opponent: Opponent,
+    trials: usize,
+    seed: u64,
+) -> Result<Distribution> {
+    ensure!(trials > 0, "No games to play");
+
+    let weights = opponent.weights(rules, input);
+    let meanings = input
+        .iter()
+        .map(|round| {
+            let (_, meaning) = interpretation
+                .iter()
+                .find(|(l, _)| *l == round.me)
+                .with_context(|| format!("Letter `{}` has no meaning", round.me))?;
+            Ok(*meaning)
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let expected = meanings
+        .iter()
+        .flat_map(|meaning| {
+            weights
+                .iter()
+                .enumerate()
+                .map(|(enemy, w)| w * rules.score(Shape(enemy), *meaning) as f64)
+        })
+        .sum();
+
+    let mut rng = Rng(seed);
+    let mut play = || {
+        let r = rng.next_f64();
+        let enemy = weights
+            .iter()
+            .scan(0., |acc, w| {
+                *acc += w;
+                Some(*acc)
+            })
+            .position(|acc| r < acc)
+            .unwrap_or(weights.len() - 1);
+
+        Shape(enemy)
+    };
+
+    let mut totals: Vec<u64> = (0..trials)
+        .map(|_| meanings.iter().map(|m| rules.score(play(), *m)).sum())
+        .collect();
+    totals.sort_unstable();
+
+    let last = totals.len() - 1;
+    let mean = totals.iter().sum::<u64>() as f64 / trials as f64;
+    let variance = totals
+        .iter()
+        .map(|t| (*t as f64 - mean).powi(2))
+        .sum::<f64>()
+        / trials as f64;
+
+    Ok(Distribution {
+        expected,
+        mean,
+        std_dev: variance.sqrt(),
+        percentiles: [0, 10, 50, 90, 100].map(|p| totals[last * p / 100]),
+    })
+}
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    const DAY: u8 = 2;
+
+    type Input = Vec<Round>;
+
+    fn parse(data: &str) -> Result<Self::Input, ParseError> {
+        input(data)
+    }
+
+    fn part1(input: &Self::Input) -> Result<impl Display> {
+        match part1(&Rules::classic(), input) {
+            Some(score) => Ok(score),
+            None => bail!("The guide has letters which are not shapes"),
+        }
+    }
+
+    fn part2(input: &Self::Input) -> Result<impl Display> {
+        match part2(&Rules::classic(), input) {
+            Some(score) => Ok(score),
+            None => bail!("The guide has letters which are not outcomes"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../test.in");
+    const RPSLS: &str = include_str!("../rpsls.toml");
+
+    #[test]
+    fn input_test() {
+        assert_eq!(
+            input(EXAMPLE).unwrap(),
+            vec![
+                Round {
+                    enemy: Shape(0),
+                    me: 'Y'
+                },
+                Round {
+                    enemy: Shape(1),
+                    me: 'X'
+                },
+                Round {
+                    enemy: Shape(2),
+                    me: 'Z'
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn invalid_input() {
+        assert_eq!(
+            input("A Y\nD X"),
+            Err(ParseError::new(2, 1, "enemy play `A`, `B` or `C`"))
+        );
+        assert_eq!(
+            input("A Y Z"),
+            Err(ParseError::new(1, 3, "my play `X`, `Y` or `Z`"))
+        );
+        assert_eq!(input("AY"), Err(ParseError::new(1, 3, "` `")));
+    }
+
+    #[test]
+    fn classic_dominance() {
+        use Outcome::*;
+
+        let rules = Rules::classic();
+        let [rock, paper, scissors] = [Shape(0), Shape(1), Shape(2)];
+
+        assert_eq!(rules.outcome(paper, rock), Win);
+        assert_eq!(rules.outcome(rock, paper), Lose);
+        assert_eq!(rules.outcome(rock, scissors), Win);
+        assert_eq!(rules.outcome(scissors, scissors), Draw);
+        assert_eq!(rules.deduce(scissors, Win), rock);
+        assert_eq!(rules.deduce(rock, Lose), scissors);
+    }
+
+    #[test]
+    fn rpsls_rules() {
+        use Outcome::*;
+
+        let rules = Rules::from_toml(RPSLS).unwrap();
+        let shape = |name: &str| Shape(rules.shapes.iter().position(|s| s.name == name).unwrap());
+
+        assert_eq!(rules.shapes.len(), 5);
+        assert_eq!(rules.outcome(shape("Spock"), shape("Scissors")), Win);
+        assert_eq!(rules.outcome(shape("Lizard"), shape("Spock")), Win);
+        assert_eq!(rules.outcome(shape("Lizard"), shape("Paper")), Win);
+        assert_eq!(rules.outcome(shape("Rock"), shape("Lizard")), Win);
+        assert_eq!(rules.outcome(shape("Paper"), shape("Scissors")), Lose);
+        assert_eq!(rules.outcome(shape("Spock"), shape("Paper")), Lose);
+
+        // Every shape beats exactly two others
+        for me in 0..5 {
+            let wins = (0..5)
+                .filter(|enemy| rules.outcome(Shape(me), Shape(*enemy)) == Win)
+                .count();
+            assert_eq!(wins, 2);
+        }
+
+        // Rock vs Rock, Scissors vs Scissors and Paper vs Paper are all draws
+        let rounds = parse_with("A V\nE Z\nC X", &rules).unwrap();
+        assert_eq!(part1(&rules, &rounds), Some(4 + 6 + 5));
+
+        // `V` is not an outcome, Rock beats Scissors and Spock loses to Paper
+        assert_eq!(part2(&rules, &rounds), None);
+        assert_eq!(part2(&rules, &rounds[1..]), Some(7 + 5));
+    }
+
+    #[test]
+    fn invalid_rules() {
+        let even = RPSLS.replace(
+            "[[shapes]]\nname = \"Lizard\"\npoints = 4\nenemy = \"D\"\nme = \"Y\"\n",
+            "",
+        );
+        assert!(Rules::from_toml(&even).is_err());
+
+        let single = RPSLS
+            .split("[[shapes]]")
+            .take(2)
+            .collect::<Vec<_>>()
+            .join("[[shapes]]");
+        assert!(Rules::from_toml(&single).is_err());
+        assert!(Rules::from_toml(&RPSLS.replace("enemy = \"E\"", "enemy = \"A\"")).is_err());
+    }
+
+    #[test]
+    fn optimizer() {
+        let rules = Rules::classic();
+        let rounds = input(EXAMPLE).unwrap();
+
+        assert_eq!(permutations(3).len(), 6);
+        assert_eq!(interpretations(&rules).len(), 12);
+
+        let ((best, best_reading), (worst, _)) = optimize(&rules, &rounds).unwrap();
+
+        // Always winning: 6 * 3 points, with paper, scissors and rock played
+        assert_eq!(best, 18 + 2 + 1 + 3);
+        assert_eq!(
+            rules.describe(&best_reading),
+            "X = Scissors, Y = Paper, Z = Rock"
+        );
+        // Always losing with scissors, rock and paper played
+        assert_eq!(worst, 3 + 1 + 2);
+        assert_eq!(
+            rules.describe(&rules.outcome_reading()),
+            "X = lose, Y = draw, Z = win"
+        );
+    }
+
+    #[test]
+    fn simulation() {
+        let rules = Rules::classic();
+        let rounds = input(EXAMPLE).unwrap();
+        let reading = rules.shape_reading();
+
+        // Paper, rock and scissors played, a third of games is won, drawn and lost
+        let uniform = simulate(&rules, &rounds, &reading, Opponent::Uniform, 1000, 1).unwrap();
+        assert_eq!(uniform.expected, 6. + 9.);
+        assert!((uniform.mean - uniform.expected).abs() < 1.);
+        assert!(uniform.percentiles[0] >= 6 && uniform.percentiles[4] <= 24);
+
+        // The opponent plays just like in the guide
+        let frequency = simulate(&rules, &rounds, &reading, Opponent::Frequency, 10, 1).unwrap();
+        assert_eq!(frequency.expected, uniform.expected);
+        assert_eq!(
+            simulate(&rules, &rounds, &reading, Opponent::Uniform, 10, 7).unwrap(),
+            simulate(&rules, &rounds, &reading, Opponent::Uniform, 10, 7).unwrap()
+        );
+
+        let rounds = input("A X\nA X\nA Y").unwrap();
+        let frequency = simulate(&rules, &rounds, &reading, Opponent::Frequency, 10, 1).unwrap();
+        assert_eq!(frequency.expected, 4. + 4. + 8.);
+        assert_eq!(frequency.std_dev, 0.);
+        assert!(simulate(&rules, &rounds, &reading, Opponent::Frequency, 0, 1).is_err());
+
+        let outcomes = rules.outcome_reading();
+        let rules = Rules::from_toml(RPSLS).unwrap();
+        let rounds = parse_with("A V\nB X", &rules).unwrap();
+        assert_eq!(
+            simulate(&rules, &rounds, &outcomes, Opponent::Uniform, 10, 1)
+                .unwrap_err()
+                .to_string(),
+            "Letter `V` has no meaning"
+        );
+    }
+
+    #[test]
+    fn part1_test() {
+        assert_eq!(part1(&Rules::classic(), &input(EXAMPLE).unwrap()), Some(15));
+    }
+
+    #[test]
+    fn part2_test() {
+        assert_eq!(part2(&Rules::classic(), &input(EXAMPLE).unwrap()), Some(12));
+    }
+}
@@ -1,168 +1,148 @@
-use anyhow::{anyhow, bail, Result};
-use std::io::BufRead;
-
-#[derive(Clone, Copy, Debug)]
-enum Shape {
-    Rock,
-    Paper,
-    Scissors,
+use std::path::PathBuf;
+
+use anyhow::Context;
+use aoc_common::solution::{run, Args, Format, Solved};
+use aoc_common::Solution;
+use clap::Parser;
+use day2::{optimize, parse_with, part1, part2, simulate, Day2, Opponent, Round, Rules};
+use serde_json::json;
+
+/// Rock paper scissors
+#[derive(Parser)]
+struct Cli {
+    #[command(flatten)]
+    args: Args,
+
+    /// Plays by the rules from a TOML file instead of the classic ones, see `day2/rpsls.toml`
+    #[arg(long, value_name = "FILE")]
+    rules: Option<PathBuf>,
+
+    /// Also reports the readings of my letters giving the highest and the lowest total score
+    #[arg(long)]
+    optimize: bool,
+
+    /// Also plays both readings of the guide against the opponent and reports the scores
+    #[arg(long, value_name = "OPPONENT")]
+    simulate: Option<Opponent>,
+
+    /// Number of games played by the simulation
+    #[arg(long, value_name = "N", default_value_t = 1000)]
+    trials: usize,
+
+    /// Seed of the simulation
+    #[arg(long, value_name = "N", default_value_t = 2022)]
+    seed: u64,
 }
 
-impl Shape {
-    fn points(self) -> u128 {
-        match self {
-            Shape::Rock => 1,
-            Shape::Paper => 2,
-            Shape::Scissors => 3,
-        }
-    }
-
-    fn outcome(self, enemy: Shape) -> Outcome {
-        use Outcome::*;
-        use Shape::*;
-
-        match (self, enemy) {
-            (Rock, Rock) => Draw,
-            (Rock, Paper) => Lose,
-            (Rock, Scissors) => Win,
-            (Paper, Rock) => Win,
-            (Paper, Paper) => Draw,
-            (Paper, Scissors) => Lose,
-            (Scissors, Rock) => Lose,
-            (Scissors, Paper) => Win,
-            (Scissors, Scissors) => Draw,
-        }
+/// Plays the guide by the custom rules, returning the rounds and the number of parts the guide
+/// has no meaning for
+///
+/// Both readings of the guide are reported on their own, so a custom guide can still be optimized
+/// or simulated even if it is meant to be read only one way.
+fn run_with(rules: &Rules, args: &Args) -> anyhow::Result<(Vec<Round>, usize)> {
+    let input = aoc_common::input::read(&args.path(Day2::DAY))?;
+    let rounds = parse_with(&input, rules)?;
+
+    let mut solved = Solved::default();
+    let errors: Vec<_> = [
+        solved.solve_part(1, || {
+            part1(rules, &rounds).context("The guide has letters which are not shapes")
+        }),
+        solved.solve_part(2, || {
+            part2(rules, &rounds).context("The guide has letters which are not outcomes")
+        }),
+    ]
+    .into_iter()
+    .zip(1..)
+    .filter_map(|(result, part)| Some((part, result.err()?)))
+    .collect();
+
+    solved.report(Day2::DAY, args.format, None)?;
+    for (part, err) in &errors {
+        eprintln!("Part {part}: Error: {err}");
     }
 
-    fn deduce(self, expected: Outcome) -> Self {
-        use Outcome::*;
-        use Shape::*;
-
-        match (self, expected) {
-            (Rock, Win) => Paper,
-            (Rock, Lose) => Scissors,
-            (Rock, Draw) => Rock,
-            (Paper, Win) => Scissors,
-            (Paper, Lose) => Rock,
-            (Paper, Draw) => Paper,
-            (Scissors, Win) => Rock,
-            (Scissors, Lose) => Paper,
-            (Scissors, Draw) => Scissors,
-        }
-    }
+    Ok((rounds, errors.len()))
 }
 
-#[derive(Clone, Copy, Debug)]
-enum Hint {
-    X,
-    Y,
-    Z,
-}
+fn print_optimized(rules: &Rules, rounds: &[Round], format: Format) -> anyhow::Result<()> {
+    let Some(((best, best_reading), (worst, worst_reading))) = optimize(rules, rounds) else {
+        anyhow::bail!("No reading gives a meaning to all the letters of the guide");
+    };
 
-impl Hint {
-    fn shape(self) -> Shape {
-        use Hint::*;
-        use Shape::*;
+    let (best_reading, worst_reading) = (
+        rules.describe(&best_reading),
+        rules.describe(&worst_reading),
+    );
 
-        match self {
-            X => Rock,
-            Y => Paper,
-            Z => Scissors,
+    match format {
+        Format::Table => {
+            println!("Best: {best} with {best_reading}");
+            println!("Worst: {worst} with {worst_reading}");
         }
-    }
-
-    fn outcome(self) -> Outcome {
-        use Hint::*;
-        use Outcome::*;
+        Format::Json => {
+            let best = json!({ "score": best, "reading": best_reading });
+            let worst = json!({ "score": worst, "reading": worst_reading });
 
-        match self {
-            X => Lose,
-            Y => Draw,
-            Z => Win,
+            println!("{}", json!({ "day": 2, "best": best, "worst": worst }));
         }
     }
-}
-
-#[derive(Clone, Copy, Debug)]
-struct Round {
-    enemy: Shape,
-    me: Hint,
-}
 
-#[derive(Clone, Copy, Debug)]
-enum Outcome {
-    Win,
-    Lose,
-    Draw,
+    Ok(())
 }
 
-impl Outcome {
-    fn points(self) -> u128 {
-        match self {
-            Outcome::Win => 6,
-            Outcome::Lose => 0,
-            Outcome::Draw => 3,
+fn print_simulated(rules: &Rules, rounds: &[Round], cli: &Cli, opponent: Opponent) {
+    for reading in [rules.shape_reading(), rules.outcome_reading()] {
+        let description = rules.describe(&reading);
+        let distribution = simulate(rules, rounds, &reading, opponent, cli.trials, cli.seed);
+
+        match (cli.args.format, distribution) {
+            (Format::Table, Ok(distribution)) => print!("With {description}:\n{distribution}"),
+            (Format::Table, Err(err)) => println!("With {description}: not simulated, {err:#}"),
+            (Format::Json, Err(err)) => {
+                let simulation = json!({ "reading": description, "error": format!("{err:#}") });
+                println!("{}", json!({ "day": 2, "simulation": simulation }));
+            }
+            (Format::Json, Ok(distribution)) => {
+                let [min, p10, median, p90, max] = distribution.percentiles;
+                let distribution = json!({
+                    "reading": description,
+                    "expected": distribution.expected,
+                    "mean": distribution.mean,
+                    "std_dev": distribution.std_dev,
+                    "min": min,
+                    "p10": p10,
+                    "median": median,
+                    "p90": p90,
+                    "max": max,
+                });
+
+                println!("{}", json!({ "day": 2, "simulation": distribution }));
+            }
         }
     }
 }
 
-fn input() -> Result<Vec<Round>> {
-    std::io::stdin()
-        .lock()
-        .lines()
-        .map(|line| -> Result<_> {
-            use Hint::*;
-            use Shape::*;
-
-            let line = line?;
-            let mut split = line.split(' ');
-            let player1 = match split.next().ok_or_else(|| anyhow!("No enemy play"))? {
-                "A" => Rock,
-                "B" => Paper,
-                "C" => Scissors,
-                _ => bail!("Invalid enemy play"),
-            };
-
-            let player2 = match split.next().ok_or_else(|| anyhow!("No my play"))? {
-                "X" => X,
-                "Y" => Y,
-                "Z" => Z,
-                _ => bail!("Invalid my play"),
-            };
-
-            if split.next().is_some() {
-                bail!("Unexpected input");
-            }
-
-            Ok(Round {
-                enemy: player1,
-                me: player2,
-            })
-        })
-        .collect()
-}
+fn main() -> anyhow::Result<()> {
+    let cli = Cli::parse();
 
-fn part1(input: &[Round]) -> u128 {
-    input
-        .iter()
-        .map(|round| round.me.shape().outcome(round.enemy).points() + round.me.shape().points())
-        .sum()
-}
+    let (rules, rounds, unsolved) = match &cli.rules {
+        Some(path) => {
+            let rules = Rules::load(path)?;
+            let (rounds, unsolved) = run_with(&rules, &cli.args)?;
+            (rules, rounds, unsolved)
+        }
+        None => (Rules::classic(), run::<Day2>(&cli.args)?, 0),
+    };
 
-fn part2(input: &[Round]) -> u128 {
-    input
-        .iter()
-        .map(|round| {
-            let outcome = round.me.outcome();
-            round.enemy.deduce(round.me.outcome()).points() + outcome.points()
-        })
-        .sum()
-}
+    if cli.optimize {
+        print_optimized(&rules, &rounds, cli.args.format)?;
+    }
 
-fn main() -> Result<()> {
-    let input = input()?;
-    println!("{}", part1(&input));
-    println!("{}", part2(&input));
+    if let Some(opponent) = cli.simulate {
+        print_simulated(&rules, &rounds, &cli, opponent);
+    }
 
+    anyhow::ensure!(unsolved == 0, "{unsolved} part(s) could not be solved");
     Ok(())
 }
@@ -0,0 +1,134 @@
+use std::collections::VecDeque;
+use std::fmt::Display;
+
+use anyhow::{ensure, Result};
+use aoc_common::{ParseError, Solution, Source};
+
+fn mix(data: &mut VecDeque<(usize, isize)>) -> Result<()> {
+    // A value is moved within the others, so there have to be some
+    ensure!(data.len() > 1, "At least two values are needed for mixing");
+
+    for i in 0..data.len() {
+        //        println!(" {data:?}");
+        let i = data.iter().position(|(n, _)| *n == i).unwrap();
+        let d = data[i].1;
+
+        let n = data.remove(i).unwrap();
+        let i = i as isize + d;
+        let i = i.rem_euclid(data.len() as isize) as usize;
+
+        data.insert(i, n);
+    }
+    //    println!(" {data:?}");
+
+    Ok(())
+}
+
+fn input(input: &str) -> Result<VecDeque<(usize, isize)>, ParseError> {
+    let source = Source::new(input);
+
+    let data = aoc_common::input::non_empty_lines(input)
+        .map(|l| source.number(l.trim()))
+        .enumerate()
+        .map(|(i, v)| v.map(|v| (i, v)))
+        .collect::<Result<VecDeque<_>, _>>()?;
+
+    // Grove coordinates are counted from the `0` value
+    if !data.iter().any(|(_, v)| *v == 0) {
+        return Err(source.error_after(input, "value `0`"));
+    }
+
+    Ok(data)
+}
+
+fn grove_coordinates(data: &VecDeque<(usize, isize)>) -> isize {
+    let zero = data.iter().position(|(_, v)| *v == 0).unwrap();
+    data[(zero + 1000) % data.len()].1
+        + data[(zero + 2000) % data.len()].1
+        + data[(zero + 3000) % data.len()].1
+}
+
+fn part1(input: &VecDeque<(usize, isize)>) -> Result<isize> {
+    let mut data = input.clone();
+    mix(&mut data)?;
+
+    Ok(grove_coordinates(&data))
+}
+
+fn part2(input: &VecDeque<(usize, isize)>) -> Result<isize> {
+    let mut data = input.clone();
+    for (_, d) in &mut data {
+        *d *= 811589153;
+    }
+
+    for _ in 0..10 {
+        mix(&mut data)?;
+    }
+
+    Ok(grove_coordinates(&data))
+}
+
+pub struct Day20;
+
+impl Solution for Day20 {
+    const DAY: u8 = 20;
+
+    type Input = VecDeque<(usize, isize)>;
+
+    fn parse(data: &str) -> Result<Self::Input, ParseError> {
+        input(data)
+    }
+
+    fn part1(input: &Self::Input) -> Result<impl Display> {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Result<impl Display> {
+        part2(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../test.in");
+
+    #[test]
+    fn input_test() {
+        let data = input(EXAMPLE).unwrap();
+
+        assert_eq!(data.len(), 7);
+        assert_eq!(data[0], (0, 1));
+        assert_eq!(data[2], (2, -3));
+    }
+
+    #[test]
+    fn invalid_input() {
+        assert_eq!(input("1\n0\nx"), Err(ParseError::new(3, 1, "number")));
+        assert_eq!(input("1\n2\n"), Err(ParseError::new(3, 1, "value `0`")));
+    }
+
+    #[test]
+    fn mix_test() {
+        let mut data = input(EXAMPLE).unwrap();
+        mix(&mut data).unwrap();
+
+        let values: Vec<_> = data.iter().map(|(_, v)| *v).collect();
+        assert_eq!(values, vec![-2, 1, 2, -3, 4, 0, 3]);
+
+        let single = input("0\n").unwrap();
+        assert!(part1(&single).is_err());
+        assert!(part2(&single).is_err());
+    }
+
+    #[test]
+    fn part1_test() {
+        assert_eq!(part1(&input(EXAMPLE).unwrap()).unwrap(), 3);
+    }
+
+    #[test]
+    fn part2_test() {
+        assert_eq!(part2(&input(EXAMPLE).unwrap()).unwrap(), 1623178306);
+    }
+}
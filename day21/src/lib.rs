@@ -0,0 +1,472 @@
+use std::collections::HashMap;
+use std::fmt::Display;
+
+use anyhow::{bail, ensure, Context, Result};
+use aoc_common::{ParseError, Solution, Source};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Op<Idx> {
+    Num(i64),
+    Add(Idx, Idx),
+    Sub(Idx, Idx),
+    Mul(Idx, Idx),
+    Div(Idx, Idx),
+    Deref,
+}
+
+impl<Idx> Op<Idx> {
+    fn map<T>(self, f: impl Fn(Idx) -> T) -> Op<T> {
+        use Op::*;
+
+        match self {
+            Num(n) => Num(n),
+            Add(a0, a1) => Add(f(a0), f(a1)),
+            Sub(a0, a1) => Sub(f(a0), f(a1)),
+            Mul(a0, a1) => Mul(f(a0), f(a1)),
+            Div(a0, a1) => Div(f(a0), f(a1)),
+            Deref => Deref,
+        }
+    }
+}
+
+/// Operations of the monkeys, failing if the result is not an `i64` integer
+fn add(a: i64, b: i64) -> Result<i64> {
+    a.checked_add(b).context("Number overflow")
+}
+
+fn sub(a: i64, b: i64) -> Result<i64> {
+    a.checked_sub(b).context("Number overflow")
+}
+
+fn mul(a: i64, b: i64) -> Result<i64> {
+    a.checked_mul(b).context("Number overflow")
+}
+
+fn div(a: i64, b: i64) -> Result<i64> {
+    ensure!(b != 0, "Division of {a} by zero");
+    ensure!(a % b == 0, "Inexact division of {a} by {b}");
+    Ok(a / b)
+}
+
+fn eval(tree: &[Op<usize>], root: usize) -> Result<i64> {
+    let mut values = vec![None; tree.len()];
+    // Monkeys waiting for the numbers of their arguments
+    let mut waiting = vec![false; tree.len()];
+    let mut stack = vec![root];
+
+    while let Some(idx) = stack.last().copied() {
+        let mut perform = |idx: usize, i0: usize, i1: usize, f: fn(i64, i64) -> Result<i64>| {
+            if let (Some(n0), Some(n1)) = (values[i0], values[i1]) {
+                values[idx] = Some(f(n0, n1)?);
+                stack.pop();
+                return Ok(());
+            }
+
+            // Only the monkeys the evaluated one depends on can be stacked above it, so meeting
+            // a waiting monkey again means it depends on itself
+            waiting[idx] = true;
+            for arg in [i0, i1] {
+                if values[arg].is_none() {
+                    ensure!(
+                        !waiting[arg],
+                        "Calculation loop, a monkey depends on itself"
+                    );
+                    stack.push(arg);
+                }
+            }
+
+            Ok(())
+        };
+
+        match &tree[idx] {
+            Op::Num(n) => {
+                values[idx] = Some(*n);
+                stack.pop();
+            }
+            Op::Add(i0, i1) => perform(idx, *i0, *i1, add)?,
+            Op::Sub(i0, i1) => perform(idx, *i0, *i1, sub)?,
+            Op::Mul(i0, i1) => perform(idx, *i0, *i1, mul)?,
+            Op::Div(i0, i1) => perform(idx, *i0, *i1, div)?,
+            Op::Deref => bail!("Calculation loop, a monkey depends on itself"),
+        }
+    }
+
+    values[root].context("No number evaluated")
+}
+
+fn parse_op<'a>(source: Source<'a>, args: &'a str) -> Result<Op<&'a str>, ParseError> {
+    let args = args.trim();
+    let mut tokens = args.split_whitespace();
+    let arg0 = tokens.next().unwrap_or(args);
+
+    if let Ok(arg) = arg0.parse::<i64>() {
+        return Ok(Op::Num(arg));
+    }
+
+    let (op, arg1) = match (tokens.next(), tokens.next()) {
+        (Some(op), Some(arg1)) => (op, arg1),
+        _ => return Err(source.error(arg0, "number or `a op b` operation")),
+    };
+
+    match op {
+        "+" => Ok(Op::Add(arg0, arg1)),
+        "-" => Ok(Op::Sub(arg0, arg1)),
+        "*" => Ok(Op::Mul(arg0, arg1)),
+        "/" => Ok(Op::Div(arg0, arg1)),
+        _ => Err(source.error(op, "operator `+`, `-`, `*` or `/`")),
+    }
+}
+
+fn input(input: &str) -> Result<HashMap<String, Op<String>>, ParseError> {
+    let source = Source::new(input);
+
+    let monkeys = aoc_common::input::non_empty_lines(input)
+        .map(|line| {
+            let (idx, args) = source.split_once(line.trim(), ":")?;
+            Ok((idx, parse_op(source, args)?))
+        })
+        .collect::<Result<Vec<_>, ParseError>>()?;
+
+    let names: HashMap<_, _> = monkeys.iter().map(|(idx, op)| (*idx, op)).collect();
+
+    // Every referenced monkey has to exist, otherwise the evaluation would be stuck
+    for (_, op) in &monkeys {
+        if let Op::Add(arg0, arg1)
+        | Op::Sub(arg0, arg1)
+        | Op::Mul(arg0, arg1)
+        | Op::Div(arg0, arg1) = op
+        {
+            if let Some(arg) = [arg0, arg1]
+                .into_iter()
+                .find(|arg| !names.contains_key(*arg))
+            {
+                return Err(source.error(arg, "existing monkey"));
+            }
+        }
+    }
+
+    match names.get("root") {
+        Some(Op::Num(_)) | None => return Err(source.error_after(input, "`root` operation")),
+        _ => (),
+    }
+
+    if !names.contains_key("humn") {
+        return Err(source.error_after(input, "`humn` monkey"));
+    }
+
+    Ok(monkeys
+        .into_iter()
+        .map(|(idx, op)| (idx.to_owned(), op.map(str::to_owned)))
+        .collect())
+}
+
+pub struct Monkeys {
+    tree: Vec<Op<usize>>,
+    root: usize,
+    humn: usize,
+}
+
+fn preprocess(mut monkeys: HashMap<String, Op<String>>) -> Monkeys {
+    let mut names: Vec<_> = monkeys.keys().cloned().collect();
+    names.sort();
+
+    let tree: Vec<_> = names
+        .iter()
+        .map(|name| {
+            monkeys
+                .remove(name)
+                .unwrap()
+                .map(|name| names.binary_search(&name).unwrap())
+        })
+        .collect();
+
+    let root = names.binary_search(&"root".to_owned()).unwrap();
+    let humn = names.binary_search(&"humn".to_owned()).unwrap();
+
+    Monkeys { tree, root, humn }
+}
+
+fn part1(monkeys: &Monkeys) -> Result<i64> {
+    eval(&monkeys.tree, monkeys.root)
+}
+
+/// Whether the number of the monkey depends on the number of `humn`
+fn depends(monkeys: &[Op<usize>], idx: usize, humn: usize) -> bool {
+    let mut visited = vec![false; monkeys.len()];
+    let mut stack = vec![idx];
+
+    while let Some(idx) = stack.pop() {
+        if idx == humn {
+            return true;
+        }
+
+        if let Op::Add(i0, i1) | Op::Sub(i0, i1) | Op::Mul(i0, i1) | Op::Div(i0, i1) = monkeys[idx]
+        {
+            stack.extend([i0, i1].into_iter().filter(|i| !visited[*i]));
+            visited[i0] = true;
+            visited[i1] = true;
+        }
+    }
+
+    false
+}
+
+fn part2(monkeys: &Monkeys) -> Result<i64> {
+    let (monkeys, root, humn) = (&monkeys.tree, monkeys.root, monkeys.humn);
+
+    let mut transformed = vec![None; monkeys.len()];
+
+    let (i0, i1) = match monkeys[root] {
+        Op::Add(i0, i1) => (i0, i1),
+        Op::Sub(i0, i1) => (i0, i1),
+        Op::Mul(i0, i1) => (i0, i1),
+        Op::Div(i0, i1) => (i0, i1),
+        _ => unreachable!(),
+    };
+
+    // The equation is solved by reversing the operations on the `humn` side
+    match (depends(monkeys, i0, humn), depends(monkeys, i1, humn)) {
+        (true, true) => bail!("`humn` is on both sides of the `root` equation"),
+        (false, false) => bail!("`humn` is on neither side of the `root` equation"),
+        _ if i0 == humn => return eval(monkeys, i1),
+        _ if i1 == humn => return eval(monkeys, i0),
+        _ => (),
+    }
+
+    let mut stack = vec![i0, i1];
+
+    while let Some(idx) = stack.last().copied() {
+        //        println!("{stack:?}");
+        //        println!("{:?}", monkeys[idx]);
+        //        println!("{values:?}");
+        let mut perform = |idx: usize,
+                           i0: usize,
+                           i1: usize,
+                           f: fn(i64, i64) -> Result<i64>,
+                           lt: fn(/* parent */ usize, /* i1 */ usize) -> Op<usize>,
+                           rt: fn(/* parent */ usize, /* i0 */ usize) -> Op<usize>|
+         -> Result<()> {
+            if i0 == humn && i1 == humn {
+                bail!("`humn` is on both sides of an operation");
+            }
+
+            // Two cases for `humn` being one of the arguments - in such case we can just
+            // fix its tree - it shoul never be evaluated
+            if i0 == humn {
+                transformed[idx] = Some(Op::Deref);
+                transformed[i1] = Some(Op::Num(eval(monkeys, i1)?));
+                //                    transformed[i1] = Some(monkeys[i1].clone());
+                transformed[i0] = Some(lt(idx, i1));
+                stack.pop();
+                return Ok(());
+            }
+
+            if i1 == humn {
+                transformed[idx] = Some(Op::Deref);
+                transformed[i0] = Some(Op::Num(eval(monkeys, i0)?));
+                //                    transformed[i0] = Some(monkeys[i0].clone());
+                transformed[i1] = Some(rt(idx, i0));
+                stack.pop();
+                return Ok(());
+            }
+
+            let (n0, n1) = (
+                transformed.get(i0).and_then(Option::as_ref).cloned(),
+                transformed.get(i1).and_then(Option::as_ref).cloned(),
+            );
+
+            match (n0, n1) {
+                // If both sides are calculated, we can just calculate
+                (Some(Op::Num(n0)), Some(Op::Num(n1))) => {
+                    transformed[idx] = Some(Op::Num(f(n0, n1)?));
+                    stack.pop();
+                }
+                // Very special case - human lives on both expression sides
+                (Some(Op::Deref), Some(Op::Deref)) => {
+                    bail!("`humn` is on both sides of an operation")
+                }
+                // If one side is calculated, and the other side is `Deref`, we can calculate
+                // the value of the `Deref` side in terms of the other side, but our node
+                // becomes `Deref`
+                (Some(Op::Deref), Some(_)) => {
+                    transformed[idx] = Some(Op::Deref);
+                    transformed[i0] = Some(lt(idx, i1));
+                    stack.pop();
+                }
+                (Some(_), Some(Op::Deref)) => {
+                    transformed[idx] = Some(Op::Deref);
+                    transformed[i1] = Some(rt(idx, i0));
+                    stack.pop();
+                }
+                // I don't think it is ever a case, but if we have tree build up of some other
+                // trees, we will forward it - maybe there is some strange case, but it looks
+                // like it means a deref loop
+                (Some(_), Some(_)) => {
+                    transformed[idx] = Some(monkeys[idx].clone());
+                    stack.pop();
+                }
+                // Cases for calculating subnodes
+                (None, Some(_)) => {
+                    stack.push(i0);
+                }
+                (Some(_), None) => {
+                    stack.push(i1);
+                }
+                (None, None) => {
+                    stack.push(i0);
+                    stack.push(i1);
+                }
+            }
+
+            Ok(())
+        };
+
+        match &monkeys[idx] {
+            Op::Num(n) => {
+                transformed[idx] = Some(Op::Num(*n));
+                stack.pop();
+            }
+            Op::Add(i0, i1) => perform(idx, *i0, *i1, add, Op::Sub, Op::Sub)?,
+            Op::Sub(i0, i1) => perform(idx, *i0, *i1, sub, Op::Add, |p, i0| Op::Sub(i0, p))?,
+            Op::Mul(i0, i1) => perform(idx, *i0, *i1, mul, Op::Div, Op::Div)?,
+            Op::Div(i0, i1) => perform(idx, *i0, *i1, div, Op::Mul, |p, i0| Op::Div(i0, p))?,
+            _ => unreachable!(),
+        }
+    }
+
+    match (transformed[i0].clone(), transformed[i1].clone()) {
+        (Some(Op::Deref), Some(tree)) => transformed[i0] = Some(tree),
+        (Some(tree), Some(Op::Deref)) => transformed[i1] = Some(tree),
+        _ => bail!("`humn` is not on exactly one side of the `root` equation"),
+    };
+
+    let transformed: Vec<_> = transformed
+        .into_iter()
+        .map(|op| op.unwrap_or(Op::Deref))
+        .collect();
+
+    eval(&transformed, humn)
+}
+
+pub struct Day21;
+
+impl Solution for Day21 {
+    const DAY: u8 = 21;
+
+    type Input = Monkeys;
+
+    fn parse(data: &str) -> Result<Self::Input, ParseError> {
+        input(data).map(preprocess)
+    }
+
+    fn part1(input: &Self::Input) -> Result<impl Display> {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Result<impl Display> {
+        part2(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../test.in");
+
+    #[test]
+    fn input_test() {
+        let monkeys = input(EXAMPLE).unwrap();
+
+        assert_eq!(monkeys.len(), 15);
+        assert_eq!(
+            monkeys["root"],
+            Op::Add("pppw".to_owned(), "sjmn".to_owned())
+        );
+        assert_eq!(monkeys["dbpl"], Op::Num(5));
+        assert_eq!(
+            monkeys["ptdq"],
+            Op::Sub("humn".to_owned(), "dvpt".to_owned())
+        );
+    }
+
+    #[test]
+    fn invalid_input() {
+        let valid = "root: humn + abcd\nhumn: 5\nabcd: 2\n";
+
+        assert!(input(valid).is_ok());
+        assert_eq!(
+            input("root: humn % abcd\nhumn: 5\nabcd: 2"),
+            Err(ParseError::new(1, 12, "operator `+`, `-`, `*` or `/`"))
+        );
+        assert_eq!(
+            input("root: humn + abcd\nhumn: 5\nabcd: x"),
+            Err(ParseError::new(3, 7, "number or `a op b` operation"))
+        );
+        assert_eq!(
+            input("root: humn + abce\nhumn: 5\nabcd: 2"),
+            Err(ParseError::new(1, 14, "existing monkey"))
+        );
+        assert_eq!(
+            input("root: 1\nhumn: 5"),
+            Err(ParseError::new(2, 8, "`root` operation"))
+        );
+        assert_eq!(
+            input(&valid.replace("humn", "hmn")),
+            Err(ParseError::new(4, 1, "`humn` monkey"))
+        );
+        assert_eq!(
+            input("root humn + abcd"),
+            Err(ParseError::new(1, 17, "`:`"))
+        );
+    }
+
+    #[test]
+    fn eval_test() {
+        let mut names: Vec<_> = input(EXAMPLE).unwrap().into_keys().collect();
+        names.sort();
+        let idx = |name: &str| names.binary_search(&name.to_owned()).unwrap();
+
+        let monkeys = preprocess(input(EXAMPLE).unwrap());
+
+        assert_eq!(eval(&monkeys.tree, idx("pppw")).unwrap(), 2);
+        assert_eq!(eval(&monkeys.tree, idx("sjmn")).unwrap(), 150);
+
+        let solve = |data| part1(&preprocess(input(data).unwrap()));
+        assert_eq!(
+            solve("root: abcd * abcd\nabcd: humn + humn\nhumn: 3").unwrap(),
+            36
+        );
+        assert_eq!(
+            solve("root: humn / abcd\nhumn: 5\nabcd: 2")
+                .unwrap_err()
+                .to_string(),
+            "Inexact division of 5 by 2"
+        );
+        assert!(solve("root: humn / abcd\nhumn: 5\nabcd: 0").is_err());
+        assert!(solve("root: humn + abcd\nhumn: 5\nabcd: root - humn").is_err());
+        assert!(solve("root: humn + abcd\nhumn: 5\nabcd: abcd * humn").is_err());
+    }
+
+    #[test]
+    fn part1_test() {
+        assert_eq!(part1(&preprocess(input(EXAMPLE).unwrap())).unwrap(), 152);
+    }
+
+    #[test]
+    fn part2_test() {
+        assert_eq!(part2(&preprocess(input(EXAMPLE).unwrap())).unwrap(), 301);
+
+        let solve = |data| part2(&preprocess(input(data).unwrap()));
+        assert_eq!(solve("root: humn + abcd\nhumn: 5\nabcd: 2").unwrap(), 2);
+        assert_eq!(
+            solve("root: abcd + efgh\nabcd: 4\nefgh: humn * two\nhumn: 5\ntwo: 2").unwrap(),
+            2
+        );
+        assert!(solve("root: humn + humn\nhumn: 5").is_err());
+        assert!(solve("root: abcd + humn\nabcd: humn * humn\nhumn: 5").is_err());
+        assert!(solve("root: abcd + abcd\nabcd: 2\nhumn: 5").is_err());
+        assert!(solve("root: abcd + efgh\nabcd: 4\nefgh: humn * humn\nhumn: 5").is_err());
+        assert!(solve("root: abcd + efgh\nabcd: 5\nefgh: humn * two\nhumn: 1\ntwo: 2").is_err());
+    }
+}
@@ -0,0 +1,424 @@
+use std::fmt::Display;
+
+use anyhow::{bail, Result};
+use aoc_common::{ParseError, Solution};
+
+pub type Ransac = Vec<u8>;
+
+/// Rucksacks, one per line
+///
+/// Malformed rucksacks are kept, so they can be reported with [`rucksack_issues`] and
+/// [`group_issues`].
+pub fn input(input: &str) -> Vec<Ransac> {
+    input.lines().map(|l| l.as_bytes().to_vec()).collect()
+}
+
+/// Size of the elf groups sharing a badge
+pub const GROUP_SIZE: usize = 3;
+
+/// Priority of the item, `None` if it is not a letter
+fn prio(item: u8) -> Option<u32> {
+    match item {
+        b'a'..=b'z' => Some((item - b'a' + 1) as u32),
+        b'A'..=b'Z' => Some((item - b'A' + 27) as u32),
+        _ => None,
+    }
+}
+
+/// Item of the priority
+fn item(prio: u32) -> char {
+    match prio {
+        1..=26 => (b'a' + prio as u8 - 1) as char,
+        _ => (b'A' + prio as u8 - 27) as char,
+    }
+}
+
+/// Set of items, bit `n` set for the item of priority `n`
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Items(u64);
+
+impl Items {
+    /// Set of every item
+    pub const ALL: Items = Items(((1 << 52) - 1) << 1);
+
+    /// Set of the items, ignoring anything not being a letter
+    pub fn new(items: &[u8]) -> Self {
+        Items(
+            items
+                .iter()
+                .filter_map(|item| prio(*item))
+                .fold(0, |set, prio| set | 1 << prio),
+        )
+    }
+
+    pub fn intersection(self, other: Items) -> Items {
+        Items(self.0 & other.0)
+    }
+
+    pub fn union(self, other: Items) -> Items {
+        Items(self.0 | other.0)
+    }
+
+    pub fn len(self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    /// Priorities of the items in the set, lowest first
+    pub fn priorities(self) -> impl Iterator<Item = u32> {
+        (1..=52).filter(move |prio| self.0 & 1 << prio != 0)
+    }
+
+    /// Items in the set, lowest priority first
+    pub fn items(self) -> impl Iterator<Item = char> {
+        self.priorities().map(item)
+    }
+}
+
+/// Items found in both compartments of the rucksack
+fn shared(ransac: &[u8]) -> Items {
+    let (fst, snd) = ransac.split_at(ransac.len() / 2);
+    Items::new(fst).intersection(Items::new(snd))
+}
+
+/// Items carried by every elf of the group
+fn badges(group: &[Ransac]) -> Items {
+    group.iter().fold(Items::ALL, |common, ransac| {
+        common.intersection(Items::new(ransac))
+    })
+}
+
+/// What is wrong with a rucksack or a group of elves
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Problem {
+    /// Character which is not an item letter, at the column counted from 1
+    InvalidItem {
+        column: usize,
+        found: char,
+    },
+    /// Compartments can't be of the same size
+    OddLength(usize),
+    NoCommonItem,
+    SeveralCommonItems(Items),
+    NoBadge,
+    SeveralBadges(Items),
+    /// Trailing elves not forming a whole group
+    IncompleteGroup(usize),
+}
+
+impl Display for Problem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let list = |items: &Items| items.items().map(|i| format!("`{i}`")).collect::<Vec<_>>();
+
+        match self {
+            Problem::InvalidItem { column, found } => {
+                write!(f, "invalid item {found:?} at column {column}")
+            }
+            Problem::OddLength(len) => write!(f, "odd number of items ({len})"),
+            Problem::NoCommonItem => write!(f, "no item in both compartments"),
+            Problem::SeveralCommonItems(items) => {
+                write!(
+                    f,
+                    "several items in both compartments: {}",
+                    list(items).join(", ")
+                )
+            }
+            Problem::NoBadge => write!(f, "no badge shared by the group"),
+            Problem::SeveralBadges(items) => {
+                write!(
+                    f,
+                    "several badges shared by the group: {}",
+                    list(items).join(", ")
+                )
+            }
+            Problem::IncompleteGroup(elves) => write!(f, "incomplete group of {elves} elves"),
+        }
+    }
+}
+
+/// Problem found at the line, counted from 1
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Issue {
+    pub line: usize,
+    pub problem: Problem,
+}
+
+impl Display for Issue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}: {}", self.line, self.problem)
+    }
+}
+
+/// First character of the rucksack which is not an item letter
+fn invalid_item(line: usize, ransac: &[u8]) -> Option<Issue> {
+    let (column, found) = String::from_utf8_lossy(ransac)
+        .chars()
+        .enumerate()
+        .find(|(_, c)| !c.is_ascii_alphabetic())?;
+
+    Some(Issue {
+        line,
+        problem: Problem::InvalidItem {
+            column: column + 1,
+            found,
+        },
+    })
+}
+
+/// Rucksacks with invalid items, odd lengths or not exactly one item in both compartments
+pub fn rucksack_issues(input: &[Ransac]) -> Vec<Issue> {
+    let mut issues = vec![];
+
+    for (line, ransac) in (1..).zip(input) {
+        if let Some(issue) = invalid_item(line, ransac) {
+            issues.push(issue);
+        }
+
+        let shared = shared(ransac);
+        let problem = if ransac.len() % 2 == 1 {
+            Problem::OddLength(ransac.len())
+        } else if shared.is_empty() {
+            Problem::NoCommonItem
+        } else if shared.len() > 1 {
+            Problem::SeveralCommonItems(shared)
+        } else {
+            continue;
+        };
+
+        issues.push(Issue { line, problem });
+    }
+
+    issues
+}
+
+/// Groups of `group_size` elves not sharing exactly one badge
+///
+/// Group problems are reported at the line of the first elf of the group. Invalid items are
+/// rucksack issues, so they are not repeated for every group size.
+///
+/// # Panics
+///
+/// Panics if `group_size` is 0.
+pub fn group_issues(input: &[Ransac], group_size: usize) -> Vec<Issue> {
+    let mut issues = vec![];
+
+    for (line, group) in (1..).step_by(group_size).zip(input.chunks(group_size)) {
+        let badges = badges(group);
+        let problem = if group.len() < group_size {
+            Problem::IncompleteGroup(group.len())
+        } else if badges.is_empty() {
+            Problem::NoBadge
+        } else if badges.len() > 1 {
+            Problem::SeveralBadges(badges)
+        } else {
+            continue;
+        };
+
+        issues.push(Issue { line, problem });
+    }
+
+    issues
+}
+
+/// Issues of both the rucksacks and the groups of `group_size` elves, sorted by line
+///
+/// # Panics
+///
+/// Panics if `group_size` is 0.
+pub fn issues(input: &[Ransac], group_size: usize) -> Vec<Issue> {
+    let mut issues = rucksack_issues(input);
+    issues.extend(group_issues(input, group_size));
+    issues.sort();
+    issues
+}
+
+/// Fails with all the issues listed, if there are any
+pub fn check(issues: &[Issue]) -> Result<()> {
+    if issues.is_empty() {
+        return Ok(());
+    }
+
+    let listed: Vec<_> = issues.iter().map(|issue| format!("  {issue}")).collect();
+    bail!(
+        "Found {} problem(s) in the input, use --lenient to ignore them:\n{}",
+        issues.len(),
+        listed.join("\n")
+    )
+}
+
+/// Sum of the priorities of items in both compartments
+///
+/// If a rucksack has several such items, the lowest priority one is taken.
+pub fn part1(input: &[Ransac]) -> u128 {
+    input
+        .iter()
+        .filter_map(|ransac| shared(ransac).priorities().next())
+        .map(u128::from)
+        .sum()
+}
+
+/// Sum of the badge priorities of consecutive groups of `group_size` elves
+///
+/// If a group shares several items, the lowest priority one is its badge. Trailing elves not
+/// forming a whole group are ignored.
+///
+/// # Panics
+///
+/// Panics if `group_size` is 0.
+pub fn part2(input: &[Ransac], group_size: usize) -> u128 {
+    input
+        .chunks_exact(group_size)
+        .filter_map(|group| badges(group).priorities().next())
+        .map(u128::from)
+        .sum()
+}
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    const DAY: u8 = 3;
+
+    type Input = Vec<Ransac>;
+
+    fn parse(data: &str) -> Result<Self::Input, ParseError> {
+        Ok(input(data))
+    }
+
+    fn part1(input: &Self::Input) -> Result<impl Display> {
+        check(&rucksack_issues(input))?;
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input) -> Result<impl Display> {
+        let mut issues: Vec<_> = (1..)
+            .zip(input)
+            .filter_map(|(line, ransac)| invalid_item(line, ransac))
+            .chain(group_issues(input, GROUP_SIZE))
+            .collect();
+        issues.sort();
+
+        check(&issues)?;
+        Ok(part2(input, GROUP_SIZE))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../test.in");
+
+    #[test]
+    fn input_test() {
+        let ransacs = input(EXAMPLE);
+
+        assert_eq!(ransacs.len(), 6);
+        assert_eq!(ransacs[0], b"vJrwpWtwJgWrhcsFMMfFFhFp");
+    }
+
+    #[test]
+    fn invalid_input() {
+        let ransacs = input("abcAbcBC\nab1b\nabcab\nabAB\naabb\ncdcx\nab");
+        let issue = |line, problem| Issue { line, problem };
+
+        assert_eq!(
+            rucksack_issues(&ransacs),
+            [
+                issue(1, Problem::SeveralCommonItems(Items::new(b"bc"))),
+                issue(
+                    2,
+                    Problem::InvalidItem {
+                        column: 3,
+                        found: '1'
+                    }
+                ),
+                issue(3, Problem::OddLength(5)),
+                issue(4, Problem::NoCommonItem),
+                issue(5, Problem::NoCommonItem),
+                issue(7, Problem::NoCommonItem),
+            ]
+        );
+        assert_eq!(
+            group_issues(&ransacs, GROUP_SIZE),
+            [
+                issue(1, Problem::SeveralBadges(Items::new(b"ab"))),
+                issue(4, Problem::NoBadge),
+                issue(7, Problem::IncompleteGroup(1)),
+            ]
+        );
+        let invalid = issue(
+            1,
+            Problem::InvalidItem {
+                column: 3,
+                found: '1',
+            },
+        );
+        assert_eq!(
+            issues(&input("ab1bc\nab"), GROUP_SIZE),
+            [
+                invalid.clone(),
+                issue(1, Problem::OddLength(5)),
+                issue(1, Problem::IncompleteGroup(2)),
+                issue(2, Problem::NoCommonItem),
+            ]
+        );
+        assert_eq!(invalid.to_string(), "line 1: invalid item '1' at column 3");
+        assert_eq!(
+            issue(1, Problem::SeveralCommonItems(Items::new(b"bc"))).to_string(),
+            "line 1: several items in both compartments: `b`, `c`"
+        );
+
+        // Invalid data is refused by the parts, but can still be computed leniently
+        assert!(Day3::part1(&ransacs).is_err());
+        assert!(Day3::part2(&ransacs).is_err());
+        assert!(Day3::part2(&input("aa1a\naaaa\naaaa")).is_err());
+        assert_eq!(part1(&ransacs), 2 + 2 + 1 + 3);
+        assert!(Day3::part1(&input(EXAMPLE)).is_ok());
+    }
+
+    #[test]
+    fn prio_test() {
+        assert_eq!(prio(b'a'), Some(1));
+        assert_eq!(prio(b'z'), Some(26));
+        assert_eq!(prio(b'A'), Some(27));
+        assert_eq!(prio(b'Z'), Some(52));
+        assert_eq!(prio(b'1'), None);
+        assert_eq!(item(1), 'a');
+        assert_eq!(item(52), 'Z');
+    }
+
+    #[test]
+    fn items_test() {
+        let a = Items::new(b"abcZ");
+        let b = Items::new(b"cdZZ");
+
+        assert_eq!(a.len(), 4);
+        assert_eq!(a.intersection(b), Items::new(b"Zc"));
+        assert_eq!(a.union(b), Items::new(b"abcdZ"));
+        assert_eq!(
+            a.union(b).priorities().collect::<Vec<_>>(),
+            [1, 2, 3, 4, 52]
+        );
+        assert!(Items::new(b"ab").intersection(Items::new(b"AB")).is_empty());
+        assert_eq!(Items::ALL.len(), 52);
+        assert_eq!(badges(&[]), Items::ALL);
+    }
+
+    #[test]
+    fn part1_test() {
+        assert_eq!(part1(&input(EXAMPLE)), 157);
+    }
+
+    #[test]
+    fn part2_test() {
+        let ransacs = input(EXAMPLE);
+
+        assert_eq!(part2(&ransacs, GROUP_SIZE), 70);
+        // Pairs share several items, the lowest priority ones are `f`, `q` and `G`
+        assert_eq!(part2(&ransacs, 2), 6 + 17 + 33);
+        assert_eq!(part2(&ransacs, 6), 0);
+    }
+}
@@ -1,65 +1,78 @@
-use std::io::BufRead;
+use std::num::NonZeroUsize;
 
-type Ransac = Vec<u8>;
+use aoc_common::solution::{run, Args, Format, Solved};
+use aoc_common::Solution;
+use clap::Parser;
+use day3::{check, group_issues, input, issues, part1, part2, Day3, Issue, Ransac, GROUP_SIZE};
+use serde_json::json;
 
-fn input() -> Vec<Ransac> {
-    std::io::stdin()
-        .lock()
-        .lines()
-        .filter_map(|l| l.ok())
-        .map(String::into_bytes)
-        .collect()
+/// Rucksack reorganization
+#[derive(Parser)]
+struct Cli {
+    #[command(flatten)]
+    args: Args,
+
+    /// Also reports the sum of badge priorities for groups of N elves
+    #[arg(long, value_name = "N")]
+    group_size: Option<NonZeroUsize>,
+
+    /// Computes the answers even from malformed rucksacks, reporting them as warnings
+    #[arg(long)]
+    lenient: bool,
 }
 
-fn prio(item: u8) -> u128 {
-    if (b'a'..=b'z').contains(&item) {
-        (item - b'a' + 1) as u128
-    } else {
-        (item - b'A' + 27) as u128
+fn warn(issues: &[Issue]) {
+    for issue in issues {
+        eprintln!("Warning: {issue}");
     }
 }
 
-fn part1(input: &[Ransac]) -> u128 {
-    let mut input = input.to_vec();
+/// Computes the answers ignoring malformed rucksacks, returning the rucksacks
+fn run_lenient(args: &Args) -> anyhow::Result<Vec<Ransac>> {
+    let path = args.path(Day3::DAY);
+    let ransacs = input(&aoc_common::input::read(&path)?);
+    warn(&issues(&ransacs, GROUP_SIZE));
 
-    input
-        .iter_mut()
-        .filter_map(|ransack0| {
-            let mut ransack1 = ransack0.split_off(ransack0.len() / 2);
-            ransack1.sort();
+    let expected = aoc_common::answers::load(&path)?;
+    Solved::timed(|| Ok(part1(&ransacs)), || Ok(part2(&ransacs, GROUP_SIZE)))?.report(
+        Day3::DAY,
+        args.format,
+        expected.as_ref(),
+    )?;
 
-            ransack0
-                .iter()
-                .find(|fst| ransack1.binary_search(fst).is_ok())
-                .copied()
-        })
-        .map(prio)
-        .sum()
+    Ok(ransacs)
 }
 
-fn part2(input: &[Ransac]) -> u128 {
-    let mut input = input.to_vec();
+fn main() -> anyhow::Result<()> {
+    let cli = Cli::parse();
+    let ransacs = match cli.lenient {
+        true => run_lenient(&cli.args)?,
+        false => run::<Day3>(&cli.args)?,
+    };
 
-    input
-        .chunks_mut(3)
-        .filter_map(|ransack| {
-            ransack[1].sort();
-            ransack[2].sort();
+    if let Some(group_size) = cli.group_size {
+        // Rucksack issues and the groups of the puzzle size were already reported by the run
+        let issues = match group_size.get() {
+            GROUP_SIZE => vec![],
+            group_size => group_issues(&ransacs, group_size),
+        };
+        match cli.lenient {
+            true => warn(&issues),
+            false => check(&issues)?,
+        }
 
-            ransack[0]
-                .iter()
-                .find(|fst| {
-                    ransack[1].binary_search(fst).is_ok() && ransack[2].binary_search(fst).is_ok()
-                })
-                .copied()
-        })
-        .map(prio)
-        .sum()
-}
+        let badges = part2(&ransacs, group_size.get());
 
-fn main() {
-    let input = input();
+        match cli.args.format {
+            Format::Table => println!("Badges of groups of {group_size}: {badges}"),
+            Format::Json => {
+                println!(
+                    "{}",
+                    json!({ "day": 3, "group_size": group_size, "badges": badges })
+                )
+            }
+        }
+    }
 
-    println!("Part 1: {}", part1(&input));
-    println!("Part 2: {}", part2(&input));
+    Ok(())
 }
@@ -0,0 +1,218 @@
+use anyhow::Result;
+use aoc_common::interval::max_depth;
+use aoc_common::{Interval, IntervalSet, ParseError, Solution, Source};
+use std::fmt::Display;
+
+pub type Assignment = Interval<u128>;
+
+/// Assignments of a group of elves, at least one
+pub type Group = Vec<Assignment>;
+
+fn parse_range(source: &Source, rng: &str) -> Result<Assignment, ParseError> {
+    let (start, end) = source.split_once(rng, "-")?;
+    let start = source.number(start)?;
+    let end = source.number(end)?;
+
+    if start > end {
+        return Err(source.error(rng, "range start not greater than its end"));
+    }
+
+    Ok(Interval::new(start, end))
+}
+
+fn input(input: &str) -> Result<Vec<Group>, ParseError> {
+    let source = Source::new(input);
+
+    aoc_common::input::non_empty_lines(input)
+        .map(|line| {
+            line.split(',')
+                .map(|rng| parse_range(&source, rng))
+                .collect()
+        })
+        .collect()
+}
+
+/// Elves of the group assigned to the same sections, `(i, j)` with `i < j`
+fn pairs(group: &Group) -> impl Iterator<Item = (usize, usize)> + '_ {
+    (0..group.len()).flat_map(move |i| (i + 1..group.len()).map(move |j| (i, j)))
+}
+
+/// Number of groups with some assignment contained in another one
+fn part1(input: &[Group]) -> usize {
+    input
+        .iter()
+        .filter(|group| {
+            pairs(group).any(|(i, j)| group[i].contains(&group[j]) || group[j].contains(&group[i]))
+        })
+        .count()
+}
+
+/// Number of groups with any two assignments overlapping
+fn part2(input: &[Group]) -> usize {
+    input
+        .iter()
+        .filter(|group| pairs(group).any(|(i, j)| group[i].overlaps(&group[j])))
+        .count()
+}
+
+/// Sections assigned to any elf
+pub fn coverage(input: &[Group]) -> IntervalSet<u128> {
+    input.iter().flatten().copied().collect()
+}
+
+/// Sections assigned to two elves of the same group
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Overlap {
+    /// Index of the group
+    pub group: usize,
+    /// Indices of the elves within the group
+    pub elves: (usize, usize),
+    pub sections: Assignment,
+}
+
+/// Overlaps of every two elves of all the groups
+pub fn overlaps(input: &[Group]) -> impl Iterator<Item = Overlap> + '_ {
+    input.iter().enumerate().flat_map(|(idx, group)| {
+        pairs(group).filter_map(move |(i, j)| {
+            Some(Overlap {
+                group: idx,
+                elves: (i, j),
+                sections: group[i].intersection(&group[j])?,
+            })
+        })
+    })
+}
+
+/// Maximal number of elves of the group assigned to the same section, for every group
+pub fn depths(input: &[Group]) -> impl Iterator<Item = usize> + '_ {
+    input.iter().map(|group| max_depth(group))
+}
+
+pub struct Day4;
+
+impl Solution for Day4 {
+    const DAY: u8 = 4;
+
+    type Input = Vec<Group>;
+
+    fn parse(data: &str) -> Result<Self::Input, ParseError> {
+        input(data)
+    }
+
+    fn part1(input: &Self::Input) -> Result<impl Display> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input) -> Result<impl Display> {
+        Ok(part2(input))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../test.in");
+
+    #[test]
+    fn input_test() {
+        assert_eq!(
+            input(EXAMPLE).unwrap(),
+            vec![
+                [Interval::new(2, 4), Interval::new(6, 8)],
+                [Interval::new(2, 3), Interval::new(4, 5)],
+                [Interval::new(5, 7), Interval::new(7, 9)],
+                [Interval::new(2, 8), Interval::new(3, 7)],
+                [Interval::new(6, 6), Interval::new(4, 6)],
+                [Interval::new(2, 6), Interval::new(4, 8)],
+            ]
+        );
+        assert_eq!(
+            input("1-2\n1-2,3-4,5-6").unwrap(),
+            [
+                vec![Interval::new(1, 2)],
+                vec![
+                    Interval::new(1, 2),
+                    Interval::new(3, 4),
+                    Interval::new(5, 6)
+                ],
+            ]
+        );
+    }
+
+    #[test]
+    fn invalid_input() {
+        assert_eq!(input("1-2,3-4\n1-2,"), Err(ParseError::new(2, 5, "`-`")));
+        assert_eq!(input("1-2,3-x"), Err(ParseError::new(1, 7, "number")));
+        assert_eq!(
+            input("1-2,4-3"),
+            Err(ParseError::new(
+                1,
+                5,
+                "range start not greater than its end"
+            ))
+        );
+    }
+
+    #[test]
+    fn coverage_test() {
+        let groups = input(EXAMPLE).unwrap();
+        let sections = coverage(&groups);
+
+        assert_eq!(sections.intervals(), [Interval::new(2, 9)]);
+        assert_eq!(sections.len(), 8);
+
+        let groups = input("1-2,8-9\n4-5,4-4").unwrap();
+        let sections = coverage(&groups);
+        assert_eq!(sections.len(), 6);
+        assert_eq!(
+            sections.gaps().collect::<Vec<_>>(),
+            [Interval::new(3, 3), Interval::new(6, 7)]
+        );
+    }
+
+    #[test]
+    fn overlaps_test() {
+        let groups = input(EXAMPLE).unwrap();
+
+        assert_eq!(
+            overlaps(&groups)
+                .map(|overlap| (overlap.group, overlap.sections))
+                .collect::<Vec<_>>(),
+            [
+                (2, Interval::new(7, 7)),
+                (3, Interval::new(3, 7)),
+                (4, Interval::new(6, 6)),
+                (5, Interval::new(4, 6)),
+            ]
+        );
+        assert_eq!(depths(&groups).collect::<Vec<_>>(), [1, 1, 2, 2, 2, 2]);
+    }
+
+    #[test]
+    fn groups() {
+        let groups = input("1-2,3-4,5-6\n1-9,3-4,4-5\n1-2,3-5,4-6\n7-7").unwrap();
+
+        assert_eq!(part1(&groups), 1);
+        assert_eq!(part2(&groups), 2);
+        assert_eq!(depths(&groups).collect::<Vec<_>>(), [1, 3, 2, 1]);
+        assert_eq!(
+            overlaps(&groups).nth(2),
+            Some(Overlap {
+                group: 1,
+                elves: (1, 2),
+                sections: Interval::new(4, 4)
+            })
+        );
+    }
+
+    #[test]
+    fn part1_test() {
+        assert_eq!(part1(&input(EXAMPLE).unwrap()), 2);
+    }
+
+    #[test]
+    fn part2_test() {
+        assert_eq!(part2(&input(EXAMPLE).unwrap()), 4);
+    }
+}
@@ -1,60 +1,112 @@
-use anyhow::{anyhow, Result};
-use std::io::BufRead;
-use std::ops::RangeInclusive;
+use aoc_common::solution::{run, Args, Format};
+use clap::Parser;
+use day4::{coverage, depths, overlaps, Day4, Group};
+use serde_json::json;
 
-type Assignment = RangeInclusive<u128>;
+/// Camp cleanup
+#[derive(Parser)]
+struct Cli {
+    #[command(flatten)]
+    args: Args,
 
-fn parse_range(rng: &str) -> Result<Assignment> {
-    let mut parts = rng.split('-');
-    let start = parts.next().ok_or_else(|| anyhow!(""))?.parse()?;
-    let end = parts.next().ok_or_else(|| anyhow!(""))?.parse()?;
-    Ok(start..=end)
-}
+    /// Also reports the number of sections assigned to any elf, and the sections in between no
+    /// elf is assigned to
+    #[arg(long)]
+    coverage: bool,
+
+    /// Also reports the elves of a group with overlapping assignments, and how much they overlap
+    #[arg(long)]
+    overlaps: bool,
 
-fn input() -> Vec<[Assignment; 2]> {
-    std::io::stdin()
-        .lock()
-        .lines()
-        .filter_map(Result::ok)
-        .map(|line| -> Result<_> {
-            let mut parts = line.split(',');
+    /// Also reports the maximal number of elves of every group assigned to the same section
+    #[arg(long)]
+    depth: bool,
+}
 
-            let fst = parts.next().ok_or_else(|| anyhow!(""))?;
-            let fst = parse_range(fst)?;
+fn print_coverage(groups: &[Group], format: Format) {
+    let sections = coverage(groups);
+    let uncovered: Vec<_> = sections.gaps().collect();
 
-            let snd = parts.next().ok_or_else(|| anyhow!(""))?;
-            let snd = parse_range(snd)?;
+    match format {
+        Format::Table => {
+            println!("Sections covered: {}", sections.len());
+            for section in uncovered {
+                println!("  uncovered: {section} ({} sections)", section.len());
+            }
+        }
+        Format::Json => {
+            let uncovered: Vec<_> = uncovered
+                .iter()
+                .map(|section| json!({ "start": section.start, "end": section.end }))
+                .collect();
 
-            Ok([fst, snd])
-        })
-        .filter_map(Result::ok)
-        .collect()
+            println!(
+                "{}",
+                json!({ "day": 4, "covered": sections.len(), "uncovered": uncovered })
+            );
+        }
+    }
 }
 
-fn part1(input: &[[Assignment; 2]]) -> usize {
-    input
-        .iter()
-        .filter(|[fst, snd]| {
-            let c0 = fst.contains(snd.start()) && fst.contains(snd.end());
-            let c1 = snd.contains(fst.start()) && snd.contains(fst.end());
-            c0 || c1
-        })
-        .count()
+fn print_overlaps(groups: &[Group], format: Format) {
+    // Groups and elves are reported by their position in the input, counting from 1
+    for overlap in overlaps(groups) {
+        let group = overlap.group + 1;
+        let (i, j) = overlap.elves;
+        let sections = overlap.sections;
+
+        match format {
+            Format::Table => println!(
+                "Group {group}: elves {} and {} ({},{}) overlap by {} sections ({sections})",
+                i + 1,
+                j + 1,
+                groups[overlap.group][i],
+                groups[overlap.group][j],
+                sections.len()
+            ),
+            Format::Json => {
+                let overlap = json!({
+                    "group": group,
+                    "elves": [i + 1, j + 1],
+                    "start": sections.start,
+                    "end": sections.end,
+                    "sections": sections.len(),
+                });
+
+                println!("{}", json!({ "day": 4, "overlap": overlap }));
+            }
+        }
+    }
 }
 
-fn part2(input: &[[Assignment; 2]]) -> usize {
-    input
-        .iter()
-        .filter(|[fst, snd]| {
-            let c0 = fst.contains(snd.start()) || fst.contains(snd.end());
-            let c1 = snd.contains(fst.start()) || snd.contains(fst.end());
-            c0 || c1
-        })
-        .count()
+fn print_depths(groups: &[Group], format: Format) {
+    let depths: Vec<_> = depths(groups).collect();
+
+    match format {
+        Format::Table => {
+            for (group, depth) in (1..).zip(depths) {
+                println!("Group {group}: depth {depth}");
+            }
+        }
+        Format::Json => println!("{}", json!({ "day": 4, "depths": depths })),
+    }
 }
 
-fn main() {
-    let input = input();
-    println!("Part 1: {}", part1(&input));
-    println!("Part 2: {}", part2(&input));
+fn main() -> anyhow::Result<()> {
+    let cli = Cli::parse();
+    let groups = run::<Day4>(&cli.args)?;
+
+    if cli.coverage {
+        print_coverage(&groups, cli.args.format);
+    }
+
+    if cli.overlaps {
+        print_overlaps(&groups, cli.args.format);
+    }
+
+    if cli.depth {
+        print_depths(&groups, cli.args.format);
+    }
+
+    Ok(())
 }
@@ -0,0 +1,616 @@
+use std::fmt::Display;
+
+use anyhow::Result;
+use aoc_common::{Interval, ParseError, Solution, Source};
+use nom::bytes::complete::tag;
+use nom::character::complete::{digit1, multispace0};
+use nom::combinator::{map_res, verify};
+use nom::multi::many0;
+use nom::sequence::{preceded, tuple};
+use nom::IResult;
+
+/// Crate label, the text between its brackets
+pub type Crate = String;
+
+/// Crates from the bottom to the top
+pub type Stack = Vec<Crate>;
+
+/// Move of crates between stacks, indexed from 0
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Command {
+    pub count: usize,
+    pub source: usize,
+    pub target: usize,
+}
+
+impl Display for Command {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "move {} from {} to {}",
+            self.count,
+            self.source + 1,
+            self.target + 1
+        )
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Input {
+    pub stacks: Vec<Stack>,
+    pub program: Vec<Command>,
+}
+
+/// Words of the line separated by spaces, along with the columns they span, counted from 0
+fn words(line: &str) -> impl Iterator<Item = (Interval<usize>, &str)> {
+    line.split(' ')
+        .scan(0, |column, word| {
+            let start = *column;
+            *column += word.chars().count() + 1;
+            Some((start, word))
+        })
+        .filter(|(_, word)| !word.is_empty())
+        .map(|(start, word)| (Interval::new(start, start + word.chars().count() - 1), word))
+}
+
+/// Label of the crate drawn as `[label]`
+fn crate_(word: &str) -> Option<&str> {
+    let label = word.strip_prefix('[')?.strip_suffix(']')?;
+    let valid = !label.is_empty() && !label.contains(['[', ']']);
+
+    valid.then_some(label)
+}
+
+/// Parses the crates diagram, returning the rest of the input after its line of stack numbers
+///
+/// Stacks are numbered from 1 by the last line of the diagram, every crate has to be drawn right
+/// above the number of its stack.
+fn stacks<'a>(source: &Source, input: &'a str) -> Result<(&'a str, Vec<Stack>), ParseError> {
+    let mut lines = input.split_inclusive('\n');
+    let mut rest = input;
+    let mut crate_lines = vec![];
+
+    let footer = loop {
+        let Some(line) = lines.next() else {
+            break "";
+        };
+        rest = &rest[line.len()..];
+
+        let line = line.trim_end_matches(['\n', '\r']);
+        if !line.contains('[') {
+            break line;
+        }
+
+        crate_lines.push(line);
+    };
+
+    let numbers: Vec<_> = words(footer)
+        .zip(1..)
+        .map(
+            |((span, word), expected)| match source.number::<usize>(word) {
+                Ok(n) if n == expected => Ok(span),
+                _ => Err(source.error(word, format!("stack number {expected}"))),
+            },
+        )
+        .collect::<Result<_, _>>()?;
+
+    let mut stacks = vec![vec![]; numbers.len()];
+    for line in crate_lines {
+        let mut placed = vec![false; numbers.len()];
+
+        for (span, word) in words(line) {
+            let label = crate_(word).ok_or_else(|| source.error(word, "`[crate]`"))?;
+
+            let mut above = numbers
+                .iter()
+                .enumerate()
+                .filter(|(_, n)| n.overlaps(&span));
+            let (Some((idx, _)), None) = (above.next(), above.next()) else {
+                return Err(source.error(word, "crate above a single stack number"));
+            };
+            if std::mem::replace(&mut placed[idx], true) {
+                return Err(source.error(word, "single crate above the stack number"));
+            }
+
+            stacks[idx].push(label.to_owned());
+        }
+    }
+
+    for stack in stacks.iter_mut() {
+        stack.reverse();
+    }
+
+    Ok((rest, stacks))
+}
+
+fn number(input: &str) -> IResult<&str, usize> {
+    map_res(digit1, str::parse)(input)
+}
+
+fn stack_number(input: &str) -> IResult<&str, usize> {
+    verify(number, |n| *n > 0)(input)
+}
+
+fn command(input: &str) -> IResult<&str, Command> {
+    let (i, (_, count, _, source, _, target)) = tuple((
+        tag("move "),
+        number,
+        tag(" from "),
+        stack_number,
+        tag(" to "),
+        stack_number,
+    ))(input)?;
+
+    Ok((
+        i,
+        Command {
+            count,
+            source: source - 1,
+            target: target - 1,
+        },
+    ))
+}
+
+fn program(input: &str) -> IResult<&str, Vec<Command>> {
+    many0(preceded(multispace0, command))(input)
+}
+
+const COMMAND: &str = "`move N from A to B` command";
+
+fn input(input: &str) -> Result<Input, ParseError> {
+    let source = Source::new(input);
+
+    let (rest, stacks) = stacks(&source, input)?;
+    let (rest, program) = program(rest).map_err(|err| source.nom_error(err, COMMAND))?;
+
+    let rest = rest.trim_start();
+    if !rest.is_empty() {
+        // The program stops on the first malformed command, parsing it again to locate the error
+        return Err(match command(rest) {
+            Err(err) => source.nom_error(err, COMMAND),
+            Ok(_) => source.error(rest, COMMAND),
+        });
+    }
+
+    Ok(Input { stacks, program })
+}
+
+/// Crane executing the rearrangement procedure
+///
+/// Cranes differ only in how many crates they lift at once, every lift keeps the order of the
+/// lifted crates.
+pub trait Crane {
+    fn name(&self) -> String;
+
+    /// Number of crates lifted at once when `count` crates are still to be moved
+    fn lift(&self, count: usize) -> usize;
+}
+
+/// Crane moving crates one at a time
+pub struct CrateMover9000;
+
+impl Crane for CrateMover9000 {
+    fn name(&self) -> String {
+        "CrateMover 9000".to_owned()
+    }
+
+    fn lift(&self, _: usize) -> usize {
+        1
+    }
+}
+
+/// Crane moving all the crates at once
+pub struct CrateMover9001;
+
+impl Crane for CrateMover9001 {
+    fn name(&self) -> String {
+        "CrateMover 9001".to_owned()
+    }
+
+    fn lift(&self, count: usize) -> usize {
+        count
+    }
+}
+
+/// Crane lifting at most the given number of crates at once
+pub struct MaxLift(pub usize);
+
+impl Crane for MaxLift {
+    fn name(&self) -> String {
+        format!("crane lifting up to {} crates", self.0)
+    }
+
+    fn lift(&self, count: usize) -> usize {
+        count.min(self.0)
+    }
+}
+
+/// What prevents a command from being executed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Problem {
+    /// Stack index out of the stacks, counted from 0
+    NoStack(usize),
+    /// Less crates on the source stack than the command moves
+    NotEnoughCrates { available: usize },
+    /// Crane lifting no crates, so the command would never finish
+    NothingLifted,
+}
+
+/// Command of the program, indexed from 0, which can't be executed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MoveError {
+    pub index: usize,
+    pub command: Command,
+    pub problem: Problem,
+}
+
+impl Display for MoveError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "command {} `{}`: ", self.index + 1, self.command)?;
+
+        match self.problem {
+            Problem::NoStack(stack) => write!(f, "there is no stack {}", stack + 1),
+            Problem::NotEnoughCrates { available } => {
+                write!(f, "only {available} crate(s) on the source stack")
+            }
+            Problem::NothingLifted => write!(f, "the crane lifts no crates"),
+        }
+    }
+}
+
+impl std::error::Error for MoveError {}
+
+/// Executes the command on the stacks, leaving them untouched on error
+fn execute(crane: &dyn Crane, stacks: &mut [Stack], command: &Command) -> Result<(), Problem> {
+    let Command {
+        mut count,
+        source,
+        target,
+    } = *command;
+
+    for stack in [source, target] {
+        if stack >= stacks.len() {
+            return Err(Problem::NoStack(stack));
+        }
+    }
+
+    let available = stacks[source].len();
+    if available < count {
+        return Err(Problem::NotEnoughCrates { available });
+    }
+
+    while count > 0 {
+        let lifted = crane.lift(count).min(count);
+        if lifted == 0 {
+            return Err(Problem::NothingLifted);
+        }
+
+        let mut moved = stacks[source].split_off(stacks[source].len() - lifted);
+        stacks[target].append(&mut moved);
+        count -= lifted;
+    }
+
+    Ok(())
+}
+
+/// Runs the whole program with the crane, calling `step` with the stacks after every command
+pub fn simulate(
+    crane: &dyn Crane,
+    input: &Input,
+    mut step: impl FnMut(usize, &[Stack]),
+) -> Result<Vec<Stack>, MoveError> {
+    let mut stacks = input.stacks.clone();
+
+    for (index, command) in input.program.iter().enumerate() {
+        execute(crane, &mut stacks, command).map_err(|problem| MoveError {
+            index,
+            command: command.clone(),
+            problem,
+        })?;
+        step(index, &stacks);
+    }
+
+    Ok(stacks)
+}
+
+/// Crates on the top of the stacks, space for an empty stack
+pub fn tops(stacks: &[Stack]) -> String {
+    stacks
+        .iter()
+        .map(|s| s.last().map_or(" ", String::as_str))
+        .collect()
+}
+
+/// Draws the stacks in the diagram format of the puzzle input, with the stack numbers
+///
+/// Columns are wide enough for the longest crate label and stack number, so the usual single
+/// letter crates in up to nine stacks are drawn just like in the puzzle input.
+pub fn render(stacks: &[Stack]) -> String {
+    let labels = stacks
+        .iter()
+        .flatten()
+        .map(|label| label.chars().count() + 2);
+    let width = labels
+        .chain([3, stacks.len().to_string().len()])
+        .max()
+        .unwrap_or(3);
+
+    let height = stacks.iter().map(Vec::len).max().unwrap_or(0);
+    let mut lines: Vec<_> = (0..height)
+        .rev()
+        .map(|level| {
+            let crates: Vec<_> = stacks
+                .iter()
+                .map(|stack| match stack.get(level) {
+                    Some(label) => format!("{:^width$}", format!("[{label}]")),
+                    None => " ".repeat(width),
+                })
+                .collect();
+
+            crates.join(" ")
+        })
+        .collect();
+
+    let numbers: Vec<_> = (1..=stacks.len()).map(|n| format!("{n:^width$}")).collect();
+    lines.push(numbers.join(" "));
+
+    lines.join("\n") + "\n"
+}
+
+/// Stacks only, to be saved as a new puzzle input
+pub fn save(stacks: &[Stack]) -> String {
+    render(stacks) + "\n"
+}
+
+fn part1(input: &Input) -> Result<String, MoveError> {
+    Ok(tops(&simulate(&CrateMover9000, input, |_, _| ())?))
+}
+
+fn part2(input: &Input) -> Result<String, MoveError> {
+    Ok(tops(&simulate(&CrateMover9001, input, |_, _| ())?))
+}
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    const DAY: u8 = 5;
+
+    type Input = Input;
+
+    fn parse(data: &str) -> Result<Self::Input, ParseError> {
+        input(data)
+    }
+
+    fn part1(input: &Self::Input) -> Result<impl Display> {
+        Ok(part1(input)?)
+    }
+
+    fn part2(input: &Self::Input) -> Result<impl Display> {
+        Ok(part2(input)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../test.in");
+
+    /// Stack of single letter crates
+    fn stack(crates: &str) -> Stack {
+        crates.chars().map(String::from).collect()
+    }
+
+    #[test]
+    fn stacks_parsing() {
+        let input = r#"    [D]    
+[N] [C]    
+[Z] [M] [P]
+ 1   2   3 
+
+"#;
+
+        let (i, stacks) = stacks(&Source::new(input), input).unwrap();
+
+        assert_eq!(i, "\n");
+        assert_eq!(stacks, vec![stack("ZN"), stack("MCD"), stack("P")]);
+    }
+
+    #[test]
+    fn wide_stacks_parsing() {
+        let input = format!(
+            "[AB]{}[X]\n\
+             [C]  [D]  [E]  [F]  [G]  [H]  [I]  [J]  [K]  [L]  [M]\n \
+             1    2    3    4    5    6    7    8    9   10   11\n",
+            " ".repeat(46)
+        );
+
+        let (_, stacks) = stacks(&Source::new(&input), &input).unwrap();
+
+        assert_eq!(stacks.len(), 11);
+        assert_eq!(stacks[0], ["C", "AB"]);
+        assert_eq!(stacks[9], ["L"]);
+        assert_eq!(stacks[10], ["M", "X"]);
+    }
+
+    #[test]
+    fn invalid_diagram() {
+        let error = |input: &str| stacks(&Source::new(input), input).unwrap_err();
+
+        assert_eq!(
+            error("[A] [B]\n 1   3 \n"),
+            ParseError::new(2, 6, "stack number 2")
+        );
+        assert_eq!(
+            error("[A] [B]\n 1 \n"),
+            ParseError::new(1, 5, "crate above a single stack number")
+        );
+        assert_eq!(
+            error("[A]     [B]\n 1   2 \n"),
+            ParseError::new(1, 9, "crate above a single stack number")
+        );
+        assert_eq!(
+            error("[A][B]\n 1   2 \n"),
+            ParseError::new(1, 1, "`[crate]`")
+        );
+        assert_eq!(
+            error("[] [B]\n 1   2 \n"),
+            ParseError::new(1, 1, "`[crate]`")
+        );
+        assert_eq!(
+            error("[AAAAA]\n 1   2 \n"),
+            ParseError::new(1, 1, "crate above a single stack number")
+        );
+    }
+
+    #[test]
+    fn input_test() {
+        let input = input(EXAMPLE).unwrap();
+
+        assert_eq!(input.stacks, vec![stack("ZN"), stack("MCD"), stack("P")]);
+        assert_eq!(input.program.len(), 4);
+        assert_eq!(
+            input.program[0],
+            Command {
+                count: 1,
+                source: 1,
+                target: 0
+            }
+        );
+    }
+
+    #[test]
+    fn invalid_input() {
+        let diagram = "[A]\n 1 \n\n";
+
+        assert_eq!(
+            input(&format!(
+                "{diagram}move 1 from 1 to 1\nmove 1 from x to 1\n"
+            ))
+            .unwrap_err(),
+            ParseError::new(5, 13, COMMAND)
+        );
+        assert_eq!(
+            input(&format!("{diagram}move 1 from 0 to 1")).unwrap_err(),
+            ParseError::new(4, 13, COMMAND)
+        );
+        assert_eq!(
+            input(&format!("{diagram}mov 1 from 1 to 1")).unwrap_err(),
+            ParseError::new(4, 1, COMMAND)
+        );
+    }
+
+    #[test]
+    fn cranes() {
+        let input = input(EXAMPLE).unwrap();
+
+        // Lifting two crates at once, `N` and `D` are moved together and then `Z` on top of them
+        assert_eq!(
+            tops(&simulate(&MaxLift(2), &input, |_, _| ()).unwrap()),
+            "MCZ"
+        );
+        assert_eq!(
+            tops(&simulate(&MaxLift(1), &input, |_, _| ()).unwrap()),
+            "CMZ"
+        );
+
+        let mut steps = vec![];
+        simulate(&CrateMover9001, &input, |idx, stacks| {
+            steps.push((idx, render(stacks)))
+        })
+        .unwrap();
+
+        assert_eq!(steps.len(), 4);
+        assert_eq!(
+            steps[0],
+            (
+                0,
+                "[D]        \n[N] [C]    \n[Z] [M] [P]\n 1   2   3 \n".to_owned()
+            )
+        );
+        assert_eq!(
+            render(&input.stacks),
+            EXAMPLE.split("\n\n").next().unwrap().to_owned() + "\n"
+        );
+    }
+
+    #[test]
+    fn invalid_commands() {
+        let input = |program: &str| super::input(&format!("[A]\n 1 \n\n{program}")).unwrap();
+
+        let err = simulate(
+            &CrateMover9000,
+            &input("move 1 from 1 to 1\nmove 2 from 1 to 1"),
+            |_, _| (),
+        )
+        .unwrap_err();
+        assert_eq!(err.index, 1);
+        assert_eq!(err.problem, Problem::NotEnoughCrates { available: 1 });
+        assert_eq!(
+            err.to_string(),
+            "command 2 `move 2 from 1 to 1`: only 1 crate(s) on the source stack"
+        );
+
+        let err = simulate(&CrateMover9001, &input("move 1 from 1 to 2"), |_, _| ()).unwrap_err();
+        assert_eq!(err.problem, Problem::NoStack(1));
+        assert!(Day5::part2(&input("move 1 from 2 to 1")).is_err());
+
+        let err = simulate(&MaxLift(0), &input("move 1 from 1 to 1"), |_, _| ()).unwrap_err();
+        assert_eq!(err.problem, Problem::NothingLifted);
+    }
+
+    /// Random stacks of random crates with labels of up to three letters, by a xorshift generator
+    fn random_stacks(seed: &mut u64) -> Vec<Stack> {
+        let mut next = |bound: u64| {
+            *seed ^= *seed << 13;
+            *seed ^= *seed >> 7;
+            *seed ^= *seed << 17;
+            (*seed % bound) as usize
+        };
+
+        let max_label = 1 + next(3) as u64;
+
+        (0..1 + next(15))
+            .map(|_| {
+                (0..next(8))
+                    .map(|_| {
+                        let len = 1 + next(max_label);
+                        (0..len).map(|_| (b'A' + next(26) as u8) as char).collect()
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn render_roundtrip() {
+        let mut seed = 2022;
+
+        for _ in 0..1000 {
+            let stacks = random_stacks(&mut seed);
+            let rendered = render(&stacks);
+
+            assert_eq!(input(&rendered).unwrap().stacks, stacks, "{rendered}");
+            let width = rendered.lines().next().unwrap().len();
+            assert!(rendered.lines().all(|l| l.len() == width));
+        }
+
+        let empty = vec![vec![]; 3];
+        assert_eq!(render(&empty), " 1   2   3 \n");
+        assert_eq!(input(&save(&empty)).unwrap().stacks, empty);
+
+        let saved = input(&save(&input(EXAMPLE).unwrap().stacks)).unwrap();
+        assert_eq!(saved.stacks, input(EXAMPLE).unwrap().stacks);
+        assert!(saved.program.is_empty());
+    }
+
+    #[test]
+    fn part1_test() {
+        assert_eq!(part1(&input(EXAMPLE).unwrap()).unwrap(), "CMZ");
+    }
+
+    #[test]
+    fn part2_test() {
+        assert_eq!(part2(&input(EXAMPLE).unwrap()).unwrap(), "MCD");
+    }
+}
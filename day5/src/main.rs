@@ -1,184 +1,96 @@
-use std::io::BufRead;
-
-use anyhow::Result;
-use nom::branch::alt;
-use nom::bytes::complete::{tag, take};
-use nom::character::complete::{char as char_, digit1, multispace0, multispace1};
-use nom::multi::{many0, separated_list0};
-use nom::sequence::{delimited, terminated, tuple};
-use nom::{IResult, Parser};
-
-type Stack = Vec<char>;
-
-#[derive(Clone, Debug)]
-struct Command {
-    count: usize,
-    source: usize,
-    target: usize,
+use std::path::{Path, PathBuf};
+
+use anyhow::Context;
+use aoc_common::solution::{run, Args, Format};
+use clap::Parser;
+use day5::{
+    render, save, simulate, tops, Crane, CrateMover9000, CrateMover9001, Day5, Input, MaxLift,
+};
+use serde_json::json;
+
+/// Supply stacks
+#[derive(Parser)]
+struct Cli {
+    #[command(flatten)]
+    args: Args,
+
+    /// Also reports the top crates after rearranging with a crane lifting up to N crates at once
+    #[arg(long, value_name = "N")]
+    max_lift: Option<usize>,
+
+    /// Also draws the stacks after every command, for every crane
+    #[arg(long)]
+    trace: bool,
+
+    /// Saves the stacks rearranged by the CrateMover 9001, or by the custom crane if `--max-lift` is
+    /// given, as a new puzzle input with no commands
+    #[arg(long, value_name = "FILE")]
+    save: Option<PathBuf>,
 }
 
-#[derive(Clone, Debug)]
-struct Input {
-    stacks: Vec<Stack>,
-    program: Vec<Command>,
-}
-
-fn some_crate_parser(input: &str) -> IResult<&str, Option<char>> {
-    let (i, c) = delimited(char_('['), take(1usize), char_(']'))(input)?;
-    Ok((i, c.chars().next()))
-}
-
-fn crate_(input: &str) -> IResult<&str, Option<char>> {
-    let none_parser = tag("   ").map(|_| None);
+fn trace(crane: &dyn Crane, input: &Input, format: Format) -> anyhow::Result<()> {
+    let name = crane.name();
 
-    alt((some_crate_parser, none_parser))(input)
-}
-
-fn crates_lines(input: &str) -> IResult<&str, Vec<Vec<Option<char>>>> {
-    many0(terminated(separated_list0(char_(' '), crate_), char_('\n')))(input)
-}
-
-fn stacks(input: &str) -> IResult<&str, Vec<Stack>> {
-    let description_line = delimited(
-        multispace0,
-        separated_list0(multispace1, digit1),
-        multispace0,
-    );
-
-    let (i, lines) = terminated(crates_lines, description_line)(input)?;
-
-    let cnt = match lines.get(0) {
-        Some(v) => v.len(),
-        None => return Ok((i, vec![])),
-    };
-
-    let mut stack = lines
-        .into_iter()
-        .fold(vec![vec![]; cnt], |mut stacks, line| {
-            for (stack, item) in stacks.iter_mut().zip(line) {
-                if let Some(c) = item {
-                    stack.push(c);
-                }
-            }
-
-            stacks
-        });
-
-    for s in stack.iter_mut() {
-        s.reverse();
+    if format == Format::Table {
+        println!("{name}:\n\n{}", render(&input.stacks));
     }
 
-    Ok((i, stack))
-}
+    simulate(crane, input, |idx, stacks| match format {
+        Format::Table => println!("{}\n\n{}", input.program[idx], render(stacks)),
+        Format::Json => {
+            let step = json!({
+                "crane": name,
+                "command": idx + 1,
+                "diagram": render(stacks),
+            });
 
-fn command(input: &str) -> IResult<&str, Command> {
-    let (i, (_, count, _, source, _, target)) = tuple((
-        tag("move "),
-        digit1,
-        tag(" from "),
-        digit1,
-        tag(" to "),
-        digit1,
-    ))(input)?;
-
-    let count: usize = count.parse().unwrap();
-    let source: usize = source.parse().unwrap();
-    let target: usize = target.parse().unwrap();
-
-    Ok((
-        i,
-        Command {
-            count,
-            source: source - 1,
-            target: target - 1,
-        },
-    ))
-}
+            println!("{}", json!({ "day": 5, "step": step }));
+        }
+    })?;
 
-fn program(input: &str) -> IResult<&str, Vec<Command>> {
-    separated_list0(char_('\n'), command)(input)
+    Ok(())
 }
 
-fn input() -> Result<Input> {
-    let s: Vec<_> = std::io::stdin().lock().lines().collect::<Result<_, _>>()?;
-    let s = s.join("\n");
-
-    let (_, (stacks, program)) = tuple((stacks, program))(&s).map_err(|err| err.to_owned())?;
+fn save_to(path: &Path, crane: &dyn Crane, input: &Input) -> anyhow::Result<()> {
+    let stacks = simulate(crane, input, |_, _| ())?;
 
-    Ok(Input { stacks, program })
+    std::fs::write(path, save(&stacks))
+        .with_context(|| format!("Cannot save stacks to {}", path.display()))
 }
 
-fn part1(input: &Input) -> String {
-    let mut stacks = input.stacks.clone();
+fn main() -> anyhow::Result<()> {
+    let cli = Cli::parse();
+    let input = run::<Day5>(&cli.args)?;
 
-    for command in &input.program {
-        let idx = stacks[command.source].len() - command.count;
-        let mut moved = stacks[command.source].split_off(idx);
-        moved.reverse();
-        stacks[command.target].append(&mut moved);
-    }
+    let custom = cli.max_lift.map(MaxLift);
 
-    stacks.iter().map(|s| s.last().unwrap_or(&' ')).collect()
-}
-
-fn part2(input: &Input) -> String {
-    let mut stacks = input.stacks.clone();
+    if cli.trace {
+        trace(&CrateMover9000, &input, cli.args.format)?;
+        trace(&CrateMover9001, &input, cli.args.format)?;
 
-    for command in &input.program {
-        let idx = stacks[command.source].len() - command.count;
-        let mut moved = stacks[command.source].split_off(idx);
-        stacks[command.target].append(&mut moved);
+        if let Some(crane) = &custom {
+            trace(crane, &input, cli.args.format)?;
+        }
     }
 
-    stacks.iter().map(|s| s.last().unwrap_or(&' ')).collect()
-}
-
-fn main() -> Result<()> {
-    let input = input()?;
-    println!("{}", part1(&input));
-    println!("{}", part2(&input));
-
-    Ok(())
-}
+    if let Some(crane) = &custom {
+        let tops = tops(&simulate(crane, &input, |_, _| ())?);
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn lines_parsing() {
-        let input = r#"    [D]    
-[N] [C]    
-[Z] [M] [P]
-"#;
-
-        let (i, lines) = crates_lines(input).unwrap();
-        assert_eq!(i, "");
-        assert_eq!(
-            lines,
-            vec![
-                vec![None, Some('D'), None],
-                vec![Some('N'), Some('C'), None],
-                vec![Some('Z'), Some('M'), Some('P')],
-            ]
-        );
+        match cli.args.format {
+            Format::Table => println!("Top crates with a {}: {tops}", crane.name()),
+            Format::Json => println!(
+                "{}",
+                json!({ "day": 5, "crane": crane.name(), "tops": tops })
+            ),
+        }
     }
 
-    #[test]
-    fn stacks_parsing() {
-        let input = r#"    [D]    
-[N] [C]    
-[Z] [M] [P]
- 1   2   3 
-
-"#;
-
-        let (i, stacks) = stacks(input).unwrap();
-
-        assert_eq!(i, "");
-        assert_eq!(
-            stacks,
-            vec![vec!['Z', 'N'], vec!['M', 'C', 'D'], vec!['P'],]
-        );
+    if let Some(path) = &cli.save {
+        match &custom {
+            Some(crane) => save_to(path, crane, &input)?,
+            None => save_to(path, &CrateMover9001, &input)?,
+        }
     }
+
+    Ok(())
 }
@@ -0,0 +1,49 @@
+//! Compares the sliding window marker detector with the former quadratic window scan
+//!
+//! Streams repeat `size - 1` distinct bytes and end with a byte not among them, so the only marker
+//! is the last window and both detectors have to go through the whole stream. Run with `cargo bench -p day6`.
+
+use aoc_common::bench::measure;
+use day6::{first_marker, Markers};
+
+const STREAM: usize = 1 << 20;
+const ITERATIONS: usize = 10;
+
+/// Former detector, checking every window byte against all the following ones
+fn quadratic(data: &[u8], size: usize) -> Option<usize> {
+    data.windows(size)
+        .position(|w| (0..w.len()).all(|i| !w[i + 1..].contains(&w[i])))
+        .map(|i| i + size)
+}
+
+/// Stream with the only marker of `size` distinct bytes at its end
+///
+/// Every window of the repeated bytes has a duplicate, only the last one with the final byte
+/// doesn't. There are just 256 byte values, so `size` can be at most 256.
+fn stream(size: usize) -> Vec<u8> {
+    assert!(
+        (2..=256).contains(&size),
+        "no such stream for window {size}"
+    );
+
+    let mut data: Vec<u8> = (0..STREAM).map(|i| (i % (size - 1)) as u8).collect();
+    data.push(255);
+    data
+}
+
+fn main() -> anyhow::Result<()> {
+    println!("{:>6} {:>12} {:>12}", "window", "quadratic", "linear");
+
+    for size in [4, 14, 64, 200] {
+        let data = stream(size);
+        assert_eq!(Markers::new(&data, size).collect::<Vec<_>>(), [data.len()]);
+        assert_eq!(quadratic(&data, size), first_marker(&data, size));
+
+        let old = measure(ITERATIONS, || quadratic(&data, size))?;
+        let new = measure(ITERATIONS, || first_marker(&data, size))?;
+
+        println!("{size:>6} {:>12?} {:>12?}", old.median, new.median);
+    }
+
+    Ok(())
+}
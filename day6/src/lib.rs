@@ -0,0 +1,374 @@
+use std::collections::VecDeque;
+use std::fmt::Display;
+use std::io::{self, ErrorKind, Read};
+use std::ops::ControlFlow;
+
+use anyhow::{Context, Result};
+use aoc_common::{ParseError, Solution};
+
+/// Distinct characters of the start-of-packet marker
+pub const PACKET: usize = 4;
+
+/// Distinct characters of the start-of-message marker
+pub const MESSAGE: usize = 14;
+
+fn input(input: &str) -> &str {
+    input.lines().next().unwrap_or_default()
+}
+
+/// Size of the chunks the streams are read in
+const CHUNK: usize = 64 * 1024;
+
+/// Occurrences of every byte in the window
+#[derive(Debug, Clone)]
+struct Counts {
+    counts: [usize; 256],
+    /// Number of distinct bytes in the window
+    distinct: usize,
+}
+
+impl Counts {
+    fn new() -> Self {
+        Self {
+            counts: [0; 256],
+            distinct: 0,
+        }
+    }
+
+    fn add(&mut self, byte: u8) {
+        self.counts[byte as usize] += 1;
+        if self.counts[byte as usize] == 1 {
+            self.distinct += 1;
+        }
+    }
+
+    fn remove(&mut self, byte: u8) {
+        self.counts[byte as usize] -= 1;
+        if self.counts[byte as usize] == 0 {
+            self.distinct -= 1;
+        }
+    }
+}
+
+/// Positions right after every window of distinct bytes in the data
+///
+/// The window slides over the data counting occurrences of every byte in it, so every byte is
+/// visited twice no matter the window size.
+#[derive(Debug, Clone)]
+pub struct Markers<'a> {
+    data: &'a [u8],
+    size: usize,
+    counts: Counts,
+    /// Position right after the window end
+    end: usize,
+}
+
+impl<'a> Markers<'a> {
+    /// # Panics
+    ///
+    /// Panics if `size` is 0.
+    pub fn new(data: &'a [u8], size: usize) -> Self {
+        assert!(size > 0, "marker window can't be empty");
+
+        Self {
+            data,
+            size,
+            counts: Counts::new(),
+            end: 0,
+        }
+    }
+}
+
+impl Iterator for Markers<'_> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        while self.end < self.data.len() {
+            self.counts.add(self.data[self.end]);
+            if self.end >= self.size {
+                self.counts.remove(self.data[self.end - self.size]);
+            }
+
+            self.end += 1;
+            if self.counts.distinct == self.size {
+                return Some(self.end);
+            }
+        }
+
+        None
+    }
+}
+
+/// Marker detector fed with the stream byte by byte, keeping just the window
+#[derive(Debug, Clone)]
+pub struct Detector {
+    size: usize,
+    counts: Counts,
+    window: VecDeque<u8>,
+    /// Number of bytes fed so far
+    offset: usize,
+}
+
+impl Detector {
+    /// # Panics
+    ///
+    /// Panics if `size` is 0.
+    pub fn new(size: usize) -> Self {
+        assert!(size > 0, "marker window can't be empty");
+
+        Self {
+            size,
+            counts: Counts::new(),
+            window: VecDeque::with_capacity(size),
+            offset: 0,
+        }
+    }
+
+    /// Feeds the next byte of the stream, returning the offset right after it if a marker ends
+    /// there
+    pub fn push(&mut self, byte: u8) -> Option<usize> {
+        self.counts.add(byte);
+        self.window.push_back(byte);
+        if self.window.len() > self.size {
+            let removed = self.window.pop_front().unwrap();
+            self.counts.remove(removed);
+        }
+
+        self.offset += 1;
+        (self.counts.distinct == self.size).then_some(self.offset)
+    }
+}
+
+/// Offsets right after every marker in the stream, read in chunks
+///
+/// Only the current chunk and the window are kept in memory, so streams of any size can be
+/// inspected. Reading errors are yielded once, ending the iteration.
+pub struct StreamMarkers<R> {
+    reader: R,
+    detector: Detector,
+    chunk: Box<[u8]>,
+    /// Range of the chunk not yet fed to the detector
+    pos: usize,
+    len: usize,
+    done: bool,
+}
+
+impl<R: Read> StreamMarkers<R> {
+    /// # Panics
+    ///
+    /// Panics if `size` is 0.
+    pub fn new(reader: R, size: usize) -> Self {
+        Self {
+            reader,
+            detector: Detector::new(size),
+            chunk: vec![0; CHUNK].into_boxed_slice(),
+            pos: 0,
+            len: 0,
+            done: false,
+        }
+    }
+}
+
+impl<R: Read> Iterator for StreamMarkers<R> {
+    type Item = io::Result<usize>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            while self.pos < self.len {
+                let byte = self.chunk[self.pos];
+                self.pos += 1;
+
+                if let Some(offset) = self.detector.push(byte) {
+                    return Some(Ok(offset));
+                }
+            }
+
+            if self.done {
+                return None;
+            }
+
+            match self.reader.read(&mut self.chunk) {
+                Ok(0) => self.done = true,
+                Ok(len) => (self.pos, self.len) = (0, len),
+                Err(err) if err.kind() == ErrorKind::Interrupted => {}
+                Err(err) => {
+                    self.done = true;
+                    return Some(Err(err));
+                }
+            }
+        }
+    }
+}
+
+/// Feeds the stream read in chunks to all the detectors at once
+///
+/// `found` is called with the detector index and the marker offset for every marker, reading stops
+/// as soon as it breaks.
+pub fn scan(
+    mut reader: impl Read,
+    detectors: &mut [Detector],
+    mut found: impl FnMut(usize, usize) -> ControlFlow<()>,
+) -> io::Result<()> {
+    let mut chunk = vec![0; CHUNK];
+
+    loop {
+        let len = match reader.read(&mut chunk) {
+            Ok(0) => return Ok(()),
+            Ok(len) => len,
+            Err(err) if err.kind() == ErrorKind::Interrupted => continue,
+            Err(err) => return Err(err),
+        };
+
+        for byte in &chunk[..len] {
+            for (idx, detector) in detectors.iter_mut().enumerate() {
+                if let Some(offset) = detector.push(*byte) {
+                    if found(idx, offset).is_break() {
+                        return Ok(());
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Offset right after the first marker of `size` distinct bytes in the stream
+pub fn first_marker_in(reader: impl Read, size: usize) -> io::Result<Option<usize>> {
+    StreamMarkers::new(reader, size).next().transpose()
+}
+
+/// Position right after the first window of `size` distinct bytes
+pub fn first_marker(data: &[u8], size: usize) -> Option<usize> {
+    Markers::new(data, size).next()
+}
+
+pub struct Day6;
+
+impl Solution for Day6 {
+    const DAY: u8 = 6;
+
+    type Input = String;
+
+    fn parse(data: &str) -> Result<Self::Input, ParseError> {
+        Ok(input(data).to_owned())
+    }
+
+    fn part1(input: &Self::Input) -> Result<impl Display> {
+        first_marker(input.as_bytes(), PACKET).context("No start-of-packet marker")
+    }
+
+    fn part2(input: &Self::Input) -> Result<impl Display> {
+        first_marker(input.as_bytes(), MESSAGE).context("No start-of-message marker")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLES: [(&str, usize, usize); 4] = [
+        ("mjqjpqmgbljsphdztnvjfqwrcgsmlb", 7, 19),
+        ("bvwbjplbgvbhsrlpgdmjqwftvncz", 5, 23),
+        ("nppdvjthqldpwncqszvftbrmjlhg", 6, 23),
+        ("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw", 11, 26),
+    ];
+
+    #[test]
+    fn input_test() {
+        assert_eq!(
+            input(include_str!("../test.in")),
+            "bvwbjplbgvbhsrlpgdmjqwftvncz"
+        );
+    }
+
+    #[test]
+    fn all_markers() {
+        assert_eq!(Markers::new(b"aabcbcdd", 3).collect::<Vec<_>>(), [4, 7]);
+        assert_eq!(Markers::new(b"abc", 1).collect::<Vec<_>>(), [1, 2, 3]);
+        assert_eq!(
+            Markers::new(&[0, 255, 0, 128], 2).collect::<Vec<_>>(),
+            [2, 3, 4]
+        );
+        assert_eq!(first_marker(b"abab", 3), None);
+        assert_eq!(first_marker(b"ab", 3), None);
+        assert!(Day6::part1(&"aaaaa".to_owned()).is_err());
+    }
+
+    /// Reader returning at most 3 bytes at once
+    struct Trickle<'a>(&'a [u8]);
+
+    impl Read for Trickle<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            let len = buf.len().min(self.0.len()).min(3);
+            buf[..len].copy_from_slice(&self.0[..len]);
+            self.0 = &self.0[len..];
+            Ok(len)
+        }
+    }
+
+    #[test]
+    fn streaming() {
+        for (data, packet, message) in EXAMPLES {
+            assert_eq!(
+                first_marker_in(data.as_bytes(), PACKET).unwrap(),
+                Some(packet)
+            );
+            assert_eq!(
+                first_marker_in(Trickle(data.as_bytes()), MESSAGE).unwrap(),
+                Some(message)
+            );
+
+            let all: Vec<_> = StreamMarkers::new(Trickle(data.as_bytes()), 5)
+                .collect::<io::Result<_>>()
+                .unwrap();
+            assert_eq!(all, Markers::new(data.as_bytes(), 5).collect::<Vec<_>>());
+        }
+
+        assert_eq!(first_marker_in(&b"abab"[..], 3).unwrap(), None);
+        // Line ends are just bytes of the stream
+        assert_eq!(first_marker_in(&b"aaaa\nabc"[..], 4).unwrap(), Some(8));
+        assert_eq!(first_marker_in(&b"ab\r\n"[..], 4).unwrap(), Some(4));
+        assert_eq!(first_marker_in(io::empty(), 1).unwrap(), None);
+
+        let mut detectors = [Detector::new(PACKET), Detector::new(MESSAGE)];
+        let mut found = vec![];
+        let data = EXAMPLES[0].0.as_bytes();
+        scan(data, &mut detectors, |idx, offset| {
+            found.push((idx, offset));
+            match idx {
+                1 => ControlFlow::Break(()),
+                _ => ControlFlow::Continue(()),
+            }
+        })
+        .unwrap();
+        assert_eq!(found.first(), Some(&(0, 7)));
+        assert_eq!(found.last(), Some(&(1, 19)));
+
+        // A stream much longer than a chunk, with the only marker at its end
+        let long = io::repeat(b'a')
+            .take(3 * CHUNK as u64 + 7)
+            .chain(&b"bc"[..]);
+        assert_eq!(first_marker_in(long, 3).unwrap(), Some(3 * CHUNK + 9));
+    }
+
+    #[test]
+    fn part1_test() {
+        for (data, expected, _) in EXAMPLES {
+            assert_eq!(
+                first_marker(input(data).as_bytes(), PACKET),
+                Some(expected),
+                "{data}"
+            );
+        }
+    }
+
+    #[test]
+    fn part2_test() {
+        for (data, _, expected) in EXAMPLES {
+            assert_eq!(
+                first_marker(input(data).as_bytes(), MESSAGE),
+                Some(expected),
+                "{data}"
+            );
+        }
+    }
+}
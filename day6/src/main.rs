@@ -1,21 +1,141 @@
-use anyhow::Result;
+use std::num::NonZeroUsize;
+use std::ops::ControlFlow;
+use std::time::Instant;
 
-fn input() -> Result<String> {
-    let mut input = String::new();
-    std::io::stdin().read_line(&mut input)?;
-    Ok(input)
+use anyhow::Context;
+use aoc_common::solution::{run, Answers, Args, Format, Solved};
+use aoc_common::Solution;
+use clap::Parser;
+use day6::{scan, Day6, Detector, Markers, MESSAGE, PACKET};
+use serde_json::json;
+
+/// Tuning trouble
+#[derive(Parser)]
+struct Cli {
+    #[command(flatten)]
+    args: Args,
+
+    /// Also reports the positions of all the markers of N distinct characters
+    #[arg(long, value_name = "N")]
+    window: Option<NonZeroUsize>,
+
+    /// Reads the input in chunks instead of loading it whole, for streams of any size
+    #[arg(long)]
+    stream: bool,
 }
 
-fn solution(s: &str, n: usize) -> usize {
-    s.as_bytes()
-        .windows(n)
-        .position(|w| (0..w.len()).all(|i| !w[i + 1..].contains(&w[i])))
-        .map(|i| i + n)
-        .unwrap_or(0)
+/// Prints the markers of a window size one by one, as they are found
+struct MarkerPrinter {
+    window: NonZeroUsize,
+    format: Format,
+    printed: usize,
+}
+
+impl MarkerPrinter {
+    fn new(window: NonZeroUsize, format: Format) -> Self {
+        Self {
+            window,
+            format,
+            printed: 0,
+        }
+    }
+
+    fn print(&mut self, marker: usize) {
+        let window = self.window;
+
+        match (self.format, self.printed) {
+            (Format::Table, 0) => print!("Markers of {window}: {marker}"),
+            (Format::Table, _) => print!(", {marker}"),
+            (Format::Json, _) => println!(
+                "{}",
+                json!({ "day": 6, "window": window, "marker": marker })
+            ),
+        }
+
+        self.printed += 1;
+    }
+
+    fn finish(self) {
+        match (self.format, self.printed) {
+            (Format::Table, 0) => println!("Markers of {}: none", self.window),
+            (Format::Table, _) => println!(),
+            (Format::Json, _) => {}
+        }
+    }
 }
 
-fn main() {
-    let s = input().unwrap();
-    println!("Part 1: {}", solution(&s, 4));
-    println!("Part 2: {}", solution(&s, 14));
+/// Solves both parts and finds the markers of the window size in a single pass over the stream
+///
+/// The markers are printed while the stream is read, so they come before the answers.
+fn run_stream(cli: &Cli) -> anyhow::Result<()> {
+    let path = cli.args.path(Day6::DAY);
+
+    let mut detectors = vec![Detector::new(PACKET), Detector::new(MESSAGE)];
+    if let Some(window) = cli.window {
+        detectors.push(Detector::new(window.get()));
+    }
+
+    let mut printer = cli
+        .window
+        .map(|window| MarkerPrinter::new(window, cli.args.format));
+    let (mut packet, mut message) = (None, None);
+    let start = Instant::now();
+    scan(
+        aoc_common::input::open(&path)?,
+        &mut detectors,
+        |idx, offset| {
+            match (idx, printer.as_mut()) {
+                (0, _) => packet = packet.or(Some((offset, start.elapsed()))),
+                (1, _) => message = message.or(Some((offset, start.elapsed()))),
+                (_, Some(printer)) => printer.print(offset),
+                (_, None) => {}
+            }
+
+            // Markers of the window size are looked for up to the end of the stream
+            match (packet, message, &printer) {
+                (Some(_), Some(_), None) => ControlFlow::Break(()),
+                _ => ControlFlow::Continue(()),
+            }
+        },
+    )?;
+
+    if let Some(printer) = printer {
+        printer.finish();
+    }
+
+    let (packet, packet_duration) = packet.context("No start-of-packet marker")?;
+    let (message, message_duration) = message.context("No start-of-message marker")?;
+
+    let solved = Solved {
+        answers: Answers {
+            part1: Some(packet.to_string()),
+            part2: Some(message.to_string()),
+        },
+        part1: Some(packet_duration),
+        part2: Some(message_duration),
+    };
+    let expected = aoc_common::answers::load(&path)?;
+
+    solved.report(Day6::DAY, cli.args.format, expected.as_ref())
+}
+
+fn main() -> anyhow::Result<()> {
+    let cli = Cli::parse();
+
+    if cli.stream {
+        return run_stream(&cli);
+    }
+
+    let stream = run::<Day6>(&cli.args)?;
+
+    if let Some(window) = cli.window {
+        let mut printer = MarkerPrinter::new(window, cli.args.format);
+        for marker in Markers::new(stream.as_bytes(), window.get()) {
+            printer.print(marker);
+        }
+
+        printer.finish();
+    }
+
+    Ok(())
 }
@@ -0,0 +1,628 @@
+use std::collections::BTreeMap;
+use std::fmt::Display;
+
+use anyhow::{bail, Context, Result};
+use aoc_common::{ParseError, Solution, Source};
+use either::Either;
+use nom::branch::alt;
+use nom::bytes::complete::tag;
+use nom::character::complete::{alpha1, digit1};
+use nom::combinator::{consumed, map_res};
+use nom::multi::{fold_many0, many0, many1};
+use nom::sequence::{delimited, preceded, tuple};
+use nom::{IResult, Parser};
+
+/// Total size of the directories summed up in part 1
+const SMALL_DIR: u128 = 100_000;
+
+/// Size of the whole filesystem
+const DISK: u128 = 70_000_000;
+
+/// Free space needed for the update
+const UPDATE: u128 = 30_000_000;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileEntry {
+    pub name: String,
+    pub size: u128,
+}
+
+/// Command from the terminal transcript, along with its output
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Command {
+    Cd {
+        dir: String,
+    },
+    CdUp,
+    CdRoot,
+    Ls {
+        dirs: Vec<String>,
+        files: Vec<FileEntry>,
+    },
+}
+
+#[derive(Debug)]
+struct Input<'a> {
+    commands: Vec<Command>,
+    /// Text of every command with its output, for locating the commands failing to replay
+    texts: Vec<&'a str>,
+}
+
+fn fsname(input: &str) -> IResult<&str, String> {
+    many1(alt((alpha1, tag("."))))
+        .map(|s| s.concat())
+        .parse(input)
+}
+
+fn cd(input: &str) -> IResult<&str, Command> {
+    delimited(tag("$ cd "), fsname, tag("\n"))
+        .map(|dir| Command::Cd { dir })
+        .parse(input)
+}
+
+fn cdup(input: &str) -> IResult<&str, Command> {
+    tag("$ cd ..\n").map(|_| Command::CdUp).parse(input)
+}
+
+fn cdroot(input: &str) -> IResult<&str, Command> {
+    tag("$ cd /\n").map(|_| Command::CdRoot).parse(input)
+}
+
+fn ls_dir_entry(input: &str) -> IResult<&str, Either<String, FileEntry>> {
+    delimited(tag("dir "), fsname, tag("\n"))
+        .map(Either::Left)
+        .parse(input)
+}
+
+fn ls_file_entry(input: &str) -> IResult<&str, Either<String, FileEntry>> {
+    map_res(
+        tuple((digit1, tag(" "), fsname, tag("\n"))),
+        |(size, _, name, _)| -> Result<_> {
+            Ok(Either::Right(FileEntry {
+                name,
+                size: size.parse()?,
+            }))
+        },
+    )
+    .parse(input)
+}
+
+fn ls_result(input: &str) -> IResult<&str, (Vec<String>, Vec<FileEntry>)> {
+    fold_many0(
+        alt((ls_dir_entry, ls_file_entry)),
+        || (vec![], vec![]),
+        |(mut dirs, mut files), entry| {
+            match entry {
+                Either::Left(dir) => dirs.push(dir),
+                Either::Right(file) => files.push(file),
+            }
+            (dirs, files)
+        },
+    )(input)
+}
+
+fn ls(input: &str) -> IResult<&str, Command> {
+    preceded(tag("$ ls\n"), ls_result)
+        .map(|(dirs, files)| Command::Ls { dirs, files })
+        .parse(input)
+}
+
+fn input_parser(input: &str) -> IResult<&str, Input<'_>> {
+    many0(consumed(alt((ls, cdup, cdroot, cd))))
+        .map(|commands| {
+            let (texts, commands) = commands.into_iter().unzip();
+            Input { commands, texts }
+        })
+        .parse(input)
+}
+
+fn input(input: &str) -> Result<Input<'_>, ParseError> {
+    let source = Source::new(input);
+    let expected = "`$ cd` or `$ ls` command, or `ls` output entry";
+
+    let (rest, output) = input_parser(input).map_err(|err| source.nom_error(err, expected))?;
+
+    let rest = rest.trim_start();
+    if !rest.is_empty() {
+        return Err(source.error(rest, expected));
+    }
+
+    Ok(output)
+}
+
+/// Index of a node in the filesystem
+pub type NodeId = usize;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Kind {
+    Dir { children: BTreeMap<String, NodeId> },
+    File { size: u128 },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Node {
+    pub name: String,
+    /// Directory containing the node, `None` for the root and the nodes no longer in the tree
+    pub parent: Option<NodeId>,
+    pub kind: Kind,
+}
+
+impl Node {
+    pub fn is_dir(&self) -> bool {
+        matches!(self.kind, Kind::Dir { .. })
+    }
+}
+
+/// `cd` command, indexed from 0, entering an entry already known to be a file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CdError {
+    pub index: usize,
+    pub dir: String,
+}
+
+impl Display for CdError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "command {} `$ cd {}`: it is a file",
+            self.index + 1,
+            self.dir
+        )
+    }
+}
+
+impl std::error::Error for CdError {}
+
+/// Filesystem tree explored by the terminal commands
+///
+/// Directories entered with `cd` are created even if they were never listed, but entering a file
+/// is an error. Listing an entry again updates it, so a file listed with another size or listed as
+/// a directory later takes the new form. A directory listed as a file later loses everything it
+/// contained.
+#[derive(Debug, Clone)]
+pub struct FileSystem {
+    nodes: Vec<Node>,
+    /// Total size of every node, including everything within directories
+    sizes: Vec<u128>,
+}
+
+impl FileSystem {
+    pub const ROOT: NodeId = 0;
+
+    pub fn build(commands: &[Command]) -> Result<Self, CdError> {
+        let root = Node {
+            name: "/".to_owned(),
+            parent: None,
+            kind: Kind::Dir {
+                children: BTreeMap::new(),
+            },
+        };
+        let mut fs = FileSystem {
+            nodes: vec![root],
+            sizes: vec![],
+        };
+
+        let mut cwd = Self::ROOT;
+        for (index, command) in commands.iter().enumerate() {
+            match command {
+                Command::Cd { dir } => {
+                    if let Some(file) = fs.child(cwd, dir).filter(|id| !fs.nodes[*id].is_dir()) {
+                        return Err(CdError {
+                            index,
+                            dir: fs.nodes[file].name.clone(),
+                        });
+                    }
+
+                    cwd = fs.entry(cwd, dir, None);
+                }
+                // Just like in the shell, the parent of the root is the root itself
+                Command::CdUp => cwd = fs.nodes[cwd].parent.unwrap_or(Self::ROOT),
+                Command::CdRoot => cwd = Self::ROOT,
+                Command::Ls { dirs, files } => {
+                    for dir in dirs {
+                        fs.entry(cwd, dir, None);
+                    }
+
+                    for file in files {
+                        fs.entry(cwd, &file.name, Some(file.size));
+                    }
+                }
+            }
+        }
+
+        fs.sizes = fs
+            .nodes
+            .iter()
+            .map(|node| match node.kind {
+                Kind::File { size } => size,
+                Kind::Dir { .. } => 0,
+            })
+            .collect();
+
+        // Nodes are created after their parents, so going backwards every node has its total size
+        // complete before it is added to its parent
+        for id in (0..fs.nodes.len()).rev() {
+            if let Some(parent) = fs.nodes[id].parent {
+                fs.sizes[parent] += fs.sizes[id];
+            }
+        }
+
+        Ok(fs)
+    }
+
+    /// Node of the name in the directory, a file of the size or a directory for `None`
+    ///
+    /// The node is created if it doesn't exist yet, or updated if it is of another kind.
+    fn entry(&mut self, dir: NodeId, name: &str, size: Option<u128>) -> NodeId {
+        let id = match self.child(dir, name) {
+            Some(id) => id,
+            None => {
+                let id = self.nodes.len();
+                self.nodes.push(Node {
+                    name: name.to_owned(),
+                    parent: Some(dir),
+                    kind: Kind::File { size: 0 },
+                });
+
+                if let Kind::Dir { children } = &mut self.nodes[dir].kind {
+                    children.insert(name.to_owned(), id);
+                }
+
+                id
+            }
+        };
+
+        let node = &mut self.nodes[id];
+        match (&node.kind, size) {
+            (Kind::Dir { .. }, None) => {}
+            (_, None) => {
+                node.kind = Kind::Dir {
+                    children: BTreeMap::new(),
+                }
+            }
+            (_, Some(size)) => {
+                // Entries of a directory turned into a file are detached from the tree
+                if let Kind::Dir { children } =
+                    std::mem::replace(&mut node.kind, Kind::File { size })
+                {
+                    for child in children.into_values() {
+                        self.nodes[child].parent = None;
+                    }
+                }
+            }
+        }
+
+        id
+    }
+
+    pub fn node(&self, id: NodeId) -> &Node {
+        &self.nodes[id]
+    }
+
+    /// Size of the file, or the total size of everything in the directory
+    pub fn size(&self, id: NodeId) -> u128 {
+        self.sizes[id]
+    }
+
+    /// Entry of the directory with the name, `None` if there is none or the node is a file
+    pub fn child(&self, dir: NodeId, name: &str) -> Option<NodeId> {
+        match &self.nodes[dir].kind {
+            Kind::Dir { children } => children.get(name).copied(),
+            Kind::File { .. } => None,
+        }
+    }
+
+    /// Entries of the directory sorted by name, none for a file
+    pub fn children(&self, id: NodeId) -> impl Iterator<Item = NodeId> + '_ {
+        let children = match &self.nodes[id].kind {
+            Kind::Dir { children } => Some(children.values().copied()),
+            Kind::File { .. } => None,
+        };
+
+        children.into_iter().flatten()
+    }
+
+    /// All the nodes of the tree with their depth, every directory followed by its entries
+    pub fn walk(&self) -> Vec<(NodeId, usize)> {
+        let mut nodes = vec![];
+        let mut stack = vec![(Self::ROOT, 0)];
+
+        while let Some((id, depth)) = stack.pop() {
+            nodes.push((id, depth));
+            let children: Vec<_> = self.children(id).collect();
+            stack.extend(children.into_iter().rev().map(|child| (child, depth + 1)));
+        }
+
+        nodes
+    }
+
+    /// Absolute path of the node, like `/a/e`
+    pub fn path(&self, id: NodeId) -> String {
+        let mut names = vec![];
+        let mut node = id;
+
+        while let Some(parent) = self.nodes[node].parent {
+            names.push(self.nodes[node].name.as_str());
+            node = parent;
+        }
+
+        names.reverse();
+        format!("/{}", names.join("/"))
+    }
+
+    /// Node at the absolute path, the leading `/` is optional
+    pub fn lookup(&self, path: &str) -> Option<NodeId> {
+        path.split('/')
+            .filter(|name| !name.is_empty())
+            .try_fold(Self::ROOT, |dir, name| self.child(dir, name))
+    }
+
+    /// All the directories, every one followed by its entries
+    pub fn dirs(&self) -> impl Iterator<Item = NodeId> + '_ {
+        self.walk()
+            .into_iter()
+            .map(|(id, _)| id)
+            .filter(|id| self.nodes[*id].is_dir())
+    }
+
+    /// `n` largest files, the largest first
+    pub fn largest_files(&self, n: usize) -> Vec<NodeId> {
+        let mut files: Vec<_> = self
+            .walk()
+            .into_iter()
+            .map(|(id, _)| id)
+            .filter(|id| !self.nodes[*id].is_dir())
+            .collect();
+
+        files.sort_by_key(|id| std::cmp::Reverse(self.sizes[*id]));
+        files.truncate(n);
+        files
+    }
+
+    /// Directories of the total size greater than `size`
+    pub fn dirs_over(&self, size: u128) -> Vec<NodeId> {
+        self.dirs().filter(|id| self.sizes[*id] > size).collect()
+    }
+
+    /// All the directories in the order printed by `du`, every one after all its subdirectories
+    pub fn du_dirs(&self) -> Vec<NodeId> {
+        let mut dirs: Vec<_> = self
+            .walk()
+            .into_iter()
+            .filter(|(id, _)| self.nodes[*id].is_dir())
+            .collect();
+        // Deeper directories first keeps subdirectories before their parents
+        dirs.sort_by_key(|(_, depth)| std::cmp::Reverse(*depth));
+
+        dirs.into_iter().map(|(id, _)| id).collect()
+    }
+
+    /// Total sizes of the directories with their paths, as printed by `du`
+    pub fn du(&self) -> String {
+        self.du_dirs()
+            .into_iter()
+            .map(|id| format!("{}\t{}\n", self.sizes[id], self.path(id)))
+            .collect()
+    }
+
+    /// The tree drawn like in the puzzle description
+    pub fn tree(&self) -> String {
+        self.walk()
+            .into_iter()
+            .map(|(id, depth)| {
+                let node = &self.nodes[id];
+                let indent = "  ".repeat(depth);
+
+                match node.kind {
+                    Kind::Dir { .. } => format!("{indent}- {} (dir)\n", node.name),
+                    Kind::File { size } => format!("{indent}- {} (file, size={size})\n", node.name),
+                }
+            })
+            .collect()
+    }
+}
+
+fn part1(fs: &FileSystem) -> u128 {
+    fs.dirs()
+        .map(|id| fs.size(id))
+        .filter(|size| *size <= SMALL_DIR)
+        .sum()
+}
+
+fn part2(fs: &FileSystem) -> Result<u128> {
+    let used = fs.size(FileSystem::ROOT);
+    let Some(needed) = (used + UPDATE)
+        .checked_sub(DISK)
+        .filter(|needed| *needed > 0)
+    else {
+        bail!("Already enough space");
+    };
+
+    fs.dirs()
+        .map(|id| fs.size(id))
+        .filter(|size| *size >= needed)
+        .min()
+        .context("No directory big enough")
+}
+
+pub struct Day7;
+
+impl Solution for Day7 {
+    const DAY: u8 = 7;
+
+    type Input = FileSystem;
+
+    fn parse(data: &str) -> Result<Self::Input, ParseError> {
+        let input = input(data)?;
+
+        FileSystem::build(&input.commands).map_err(|err| {
+            Source::new(data).error(
+                input.texts[err.index],
+                format!("`cd` into a directory, `{}` is a file", err.dir),
+            )
+        })
+    }
+
+    fn part1(input: &Self::Input) -> Result<impl Display> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input) -> Result<impl Display> {
+        part2(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../test.in");
+
+    #[test]
+    fn input_test() {
+        let input = input(EXAMPLE).unwrap();
+
+        assert_eq!(input.commands.len(), 10);
+        assert!(matches!(input.commands[0], Command::CdRoot));
+        assert!(
+            matches!(&input.commands[1], Command::Ls { dirs, files } if dirs.len() == 2 && files.len() == 2)
+        );
+    }
+
+    #[test]
+    fn invalid_input() {
+        assert_eq!(
+            input("$ cd /\n$ ls\n12 a\nfoo b\n").unwrap_err(),
+            ParseError::new(4, 1, "`$ cd` or `$ ls` command, or `ls` output entry")
+        );
+
+        // A file can't be entered, even if it was listed as a directory before
+        let data = "$ ls\ndir a\n$ ls\n1 a\n$ cd a\n$ ls\n2 b\n";
+        assert_eq!(
+            FileSystem::build(&input(data).unwrap().commands).unwrap_err(),
+            CdError {
+                index: 2,
+                dir: "a".to_owned()
+            }
+        );
+        assert_eq!(
+            Day7::parse(data).unwrap_err(),
+            ParseError::new(5, 1, "`cd` into a directory, `a` is a file")
+        );
+        assert!(Day7::parse("$ ls\n1 a\n$ cd a.b\n").is_ok());
+    }
+
+    fn example() -> FileSystem {
+        FileSystem::build(&input(EXAMPLE).unwrap().commands).unwrap()
+    }
+
+    #[test]
+    fn unlisted_directory() {
+        let fs = FileSystem::build(
+            &input("$ cd /\n$ cd a\n$ ls\n12 b\n$ cd ..\n$ cd ..\n")
+                .unwrap()
+                .commands,
+        )
+        .unwrap();
+
+        assert_eq!(fs.size(FileSystem::ROOT), 12);
+        assert_eq!(fs.size(fs.lookup("/a").unwrap()), 12);
+        assert_eq!(fs.node(fs.lookup("a/b").unwrap()).parent, fs.lookup("/a"));
+    }
+
+    #[test]
+    fn relisting() {
+        let commands = input("$ ls\n1 a\ndir b\n$ ls\ndir a\n2 b\n$ cd a\n$ ls\n3 c\n").unwrap();
+        let fs = FileSystem::build(&commands.commands).unwrap();
+
+        assert!(fs.node(fs.lookup("/a").unwrap()).is_dir());
+        assert_eq!(fs.size(fs.lookup("/b").unwrap()), 2);
+        assert_eq!(fs.size(FileSystem::ROOT), 5);
+
+        // Everything within the directory is gone when it is listed as a file
+        let commands =
+            input("$ cd a\n$ ls\n5 x\ndir y\n$ cd y\n$ ls\n7 z\n$ cd /\n$ ls\n3 a\n").unwrap();
+        let fs = FileSystem::build(&commands.commands).unwrap();
+
+        assert_eq!(fs.size(FileSystem::ROOT), 3);
+        assert_eq!(fs.dirs_over(0), [FileSystem::ROOT]);
+        assert_eq!(fs.du(), "3\t/\n");
+        assert_eq!(fs.tree(), "- / (dir)\n  - a (file, size=3)\n");
+    }
+
+    #[test]
+    fn deep_nesting() {
+        const DEPTH: usize = 100_000;
+
+        let data = format!("{}$ ls\n1 f\n", "$ cd a\n".repeat(DEPTH));
+        let fs = FileSystem::build(&input(&data).unwrap().commands).unwrap();
+
+        assert_eq!(fs.size(FileSystem::ROOT), 1);
+        assert_eq!(fs.walk().last().map(|(_, depth)| *depth), Some(DEPTH + 1));
+        assert_eq!(part1(&fs), DEPTH as u128 + 1);
+    }
+
+    #[test]
+    fn sizes_test() {
+        let fs = example();
+        let size = |path| fs.size(fs.lookup(path).unwrap());
+
+        assert_eq!(size("/"), 48381165);
+        assert_eq!(size("/a"), 94853);
+        assert_eq!(size("/a/e"), 584);
+        assert_eq!(size("/d"), 24933642);
+        assert_eq!(size("/d/k"), 7214296);
+        assert_eq!(fs.lookup("/a/x"), None);
+        assert_eq!(fs.lookup("/b.txt/x"), None);
+    }
+
+    #[test]
+    fn queries() {
+        let fs = example();
+        let paths = |ids: Vec<NodeId>| ids.into_iter().map(|id| fs.path(id)).collect::<Vec<_>>();
+
+        assert_eq!(paths(fs.largest_files(2)), ["/b.txt", "/c.dat"]);
+        assert_eq!(paths(fs.dirs_over(100000)), ["/", "/d"]);
+        assert_eq!(paths(fs.dirs().collect()), ["/", "/a", "/a/e", "/d"]);
+        assert_eq!(fs.path(FileSystem::ROOT), "/");
+    }
+
+    #[test]
+    fn printers() {
+        let fs = example();
+
+        assert_eq!(
+            fs.tree(),
+            "- / (dir)
+  - a (dir)
+    - e (dir)
+      - i (file, size=584)
+    - f (file, size=29116)
+    - g (file, size=2557)
+    - h.lst (file, size=62596)
+  - b.txt (file, size=14848514)
+  - c.dat (file, size=8504156)
+  - d (dir)
+    - d.ext (file, size=5626152)
+    - d.log (file, size=8033020)
+    - j (file, size=4060174)
+    - k (file, size=7214296)
+"
+        );
+        assert_eq!(fs.du(), "584\t/a/e\n94853\t/a\n24933642\t/d\n48381165\t/\n");
+        assert_eq!(
+            fs.du_dirs(),
+            ["/a/e", "/a", "/d", "/"].map(|path| fs.lookup(path).unwrap())
+        );
+    }
+
+    #[test]
+    fn part1_test() {
+        assert_eq!(part1(&example()), 95437);
+    }
+
+    #[test]
+    fn part2_test() {
+        assert_eq!(part2(&example()).unwrap(), 24933642);
+    }
+}
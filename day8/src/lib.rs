@@ -0,0 +1,117 @@
+use std::fmt::Display;
+
+use anyhow::Result;
+use aoc_common::{Dir, Grid, ParseError, Solution};
+
+fn input(input: &str) -> Result<Grid<u8>, ParseError> {
+    Grid::parse(input, "tree height digit", |c| {
+        c.to_digit(10).map(|h| h as u8)
+    })
+}
+
+fn part1(m: &Grid<u8>) -> usize {
+    m.iter()
+        .filter(|&(pos, &h)| {
+            Dir::ALL
+                .into_iter()
+                .any(|dir| m.ray(pos, dir).all(|p| m[p] < h))
+        })
+        .count()
+}
+
+fn part2(m: &Grid<u8>) -> usize {
+    m.iter()
+        .map(|(pos, &h)| {
+            Dir::ALL
+                .into_iter()
+                .map(|dir| {
+                    // The view ends on the first tree at least as high, or on the edge
+                    m.ray(pos, dir)
+                        .position(|p| m[p] >= h)
+                        .map_or_else(|| m.ray(pos, dir).count(), |i| i + 1)
+                })
+                .product()
+        })
+        .max()
+        .unwrap_or(0)
+}
+
+pub struct Day8;
+
+impl Solution for Day8 {
+    const DAY: u8 = 8;
+
+    type Input = Grid<u8>;
+
+    fn parse(data: &str) -> Result<Self::Input, ParseError> {
+        input(data)
+    }
+
+    fn part1(input: &Self::Input) -> Result<impl Display> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input) -> Result<impl Display> {
+        Ok(part2(input))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn input_test() {
+        let data = r#"30373
+25512
+65332
+33549
+35390"#;
+
+        let expected = vec![
+            3, 0, 3, 7, 3, 2, 5, 5, 1, 2, 6, 5, 3, 3, 2, 3, 3, 5, 4, 9, 3, 5, 3, 9, 0,
+        ];
+
+        assert_eq!(input(data).unwrap(), Grid::from_vec(expected, 5).unwrap());
+    }
+
+    #[test]
+    fn invalid_input() {
+        assert_eq!(
+            input("123\n1a3"),
+            Err(ParseError::new(2, 2, "tree height digit"))
+        );
+        assert_eq!(
+            input("123\n1234"),
+            Err(ParseError::new(2, 4, "row of width 3"))
+        );
+        assert_eq!(
+            input("123\n12"),
+            Err(ParseError::new(2, 3, "row of width 3"))
+        );
+    }
+
+    #[test]
+    fn part1_test() {
+        let data = r#"30373
+25512
+65332
+33549
+35390"#;
+
+        let data = input(data).unwrap();
+        assert_eq!(part1(&data), 21);
+    }
+
+    #[test]
+    fn part2_test() {
+        let data = r#"30373
+25512
+65332
+33549
+35390"#;
+
+        let data = input(data).unwrap();
+        assert_eq!(part2(&data), 8);
+    }
+}
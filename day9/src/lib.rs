@@ -0,0 +1,143 @@
+use std::collections::HashSet;
+use std::fmt::Display;
+
+use anyhow::Result;
+use aoc_common::{Dir, ParseError, Pos, Solution, Source};
+
+type Move = (Dir, usize);
+
+fn input(input: &str) -> Result<Vec<Move>, ParseError> {
+    let source = Source::new(input);
+
+    aoc_common::input::non_empty_lines(input)
+        .map(|line| {
+            let (dir, dist) = source.split_once(line, " ")?;
+
+            let dir = match dir {
+                "U" => Dir::Up,
+                "D" => Dir::Down,
+                "L" => Dir::Left,
+                "R" => Dir::Right,
+                _ => return Err(source.error(dir, "direction `U`, `D`, `L` or `R`")),
+            };
+
+            let dist = source.number(dist)?;
+
+            Ok((dir, dist))
+        })
+        .collect()
+}
+
+fn tail(t: Pos<i128>, h: Pos<i128>) -> Pos<i128> {
+    if (t.x - h.x).abs() <= 1 && (t.y - h.y).abs() <= 1 {
+        t
+    } else {
+        Pos::new(t.x + (h.x - t.x).signum(), t.y + (h.y - t.y).signum())
+    }
+}
+
+fn part1(input: &[Move]) -> usize {
+    let positions: HashSet<_> = input
+        .iter()
+        .scan((Pos::default(), Pos::default()), |(t, h), (dir, dist)| {
+            Some(
+                std::iter::repeat_with(|| {
+                    *h = h.step(*dir);
+                    *t = tail(*t, *h);
+
+                    *t
+                })
+                .take(*dist)
+                .collect::<Vec<_>>(),
+            )
+        })
+        .flatten()
+        .collect();
+
+    positions.len()
+}
+
+fn part2(input: &[Move]) -> usize {
+    let positions: HashSet<_> = input
+        .iter()
+        .scan([Pos::default(); 10], |rope, (dir, dist)| {
+            Some(
+                std::iter::repeat_with(|| {
+                    rope[0] = rope[0].step(*dir);
+                    for idx in 1..10 {
+                        rope[idx] = tail(rope[idx], rope[idx - 1]);
+                    }
+
+                    rope[9]
+                })
+                .take(*dist)
+                .collect::<Vec<_>>(),
+            )
+        })
+        .flatten()
+        .collect();
+
+    positions.len()
+}
+
+pub struct Day9;
+
+impl Solution for Day9 {
+    const DAY: u8 = 9;
+
+    type Input = Vec<Move>;
+
+    fn parse(data: &str) -> Result<Self::Input, ParseError> {
+        input(data)
+    }
+
+    fn part1(input: &Self::Input) -> Result<impl Display> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input) -> Result<impl Display> {
+        Ok(part2(input))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../test.in");
+
+    #[test]
+    fn input_test() {
+        let moves = input(EXAMPLE).unwrap();
+
+        assert_eq!(moves.len(), 8);
+        assert_eq!(moves[0], (Dir::Right, 4));
+        assert_eq!(moves[3], (Dir::Down, 1));
+    }
+
+    #[test]
+    fn invalid_input() {
+        assert_eq!(
+            input("R 4\nX 1"),
+            Err(ParseError::new(2, 1, "direction `U`, `D`, `L` or `R`"))
+        );
+        assert_eq!(input("R 4\nU"), Err(ParseError::new(2, 2, "` `")));
+        assert_eq!(input("R -4"), Err(ParseError::new(1, 3, "number")));
+    }
+
+    #[test]
+    fn part1_test() {
+        assert_eq!(part1(&input(EXAMPLE).unwrap()), 13);
+    }
+
+    #[test]
+    fn part2_test() {
+        assert_eq!(part2(&input(EXAMPLE).unwrap()), 1);
+    }
+
+    #[test]
+    fn part2_larger_test() {
+        let data = "R 5\nU 8\nL 8\nD 3\nR 17\nD 10\nL 25\nU 20";
+        assert_eq!(part2(&input(data).unwrap()), 36);
+    }
+}